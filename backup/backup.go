@@ -0,0 +1,97 @@
+package backup
+
+import (
+	"github.com/timabell/disk-hog-backup/backup_sets"
+	"github.com/timabell/disk-hog-backup/dhcopy"
+	"github.com/timabell/disk-hog-backup/dherrors"
+	"log"
+	"os"
+	"path/filepath"
+	"time"
+)
+
+// Options controls what is included in a backup.
+type Options struct {
+	// SourceList limits the backup to these paths within the source folder
+	// when SourceListGiven is set. An empty list backs up nothing rather than
+	// the whole source folder.
+	SourceList      []string
+	SourceListGiven bool
+
+	// RemoveIncomplete deletes sets left unfinished by earlier runs before
+	// starting the new one. Sets that may still be being written by another
+	// run are left alone.
+	RemoveIncomplete bool
+
+	// Copy controls how individual files are copied.
+	Copy dhcopy.Options
+}
+
+// Backup copies source into a new set in dest, creating dest if needed. It
+// returns the name of the set, which is empty if there was nothing to back
+// up, and what was copied; stats is never nil, even on failure.
+func Backup(source string, dest string, options Options) (setName string, stats *dhcopy.Stats, err error) {
+	stats = &dhcopy.Stats{}
+	err = os.MkdirAll(dest, os.ModePerm)
+	if err != nil {
+		return "", stats, dherrors.Wrap(dherrors.OpWrite, dest, err)
+	}
+	if options.SourceListGiven && len(options.SourceList) == 0 {
+		log.Println("the source list is empty, nothing to back up")
+		return "", stats, nil
+	}
+	if options.RemoveIncomplete {
+		if err = removeIncompleteSets(dest); err != nil {
+			return "", stats, err
+		}
+	}
+	setName, err = backup_sets.CreateEmptySet(dest, time.Now)
+	if err != nil {
+		return "", stats, err
+	}
+	destFolder := filepath.Join(dest, setName)
+	log.Printf("backing up %v into %v\n", source, destFolder)
+	if options.SourceListGiven {
+		err = dhcopy.CopyPaths(source, destFolder, options.SourceList, options.Copy, stats)
+	} else {
+		err = dhcopy.CopyFolder(source, destFolder, options.Copy, stats)
+	}
+	if err == nil {
+		err = finalize(destFolder, options.partialReason(), stats)
+	}
+	for _, line := range stats.Summary() {
+		log.Println(line)
+	}
+	return
+}
+
+// partialReason says why a set won't hold everything in the source, or is
+// empty for a full backup.
+func (o Options) partialReason() string {
+	if o.Copy.ChangedSince.IsZero() {
+		return ""
+	}
+	return "only files changed since " + o.Copy.ChangedSince.Format("2006-01-02 15:04:05")
+}
+
+func removeIncompleteSets(dest string) error {
+	incomplete, err := backup_sets.ListIncompleteSets(dest)
+	if err != nil {
+		return err
+	}
+	for _, setName := range incomplete {
+		running, err := backup_sets.MayBeRunning(filepath.Join(dest, setName), time.Now())
+		if err != nil {
+			return err
+		}
+		if running {
+			log.Printf("leaving incomplete set %v, the backup writing it may still be running\n", setName)
+			continue
+		}
+		log.Printf("removing incomplete set %v\n", setName)
+		if err := backup_sets.DeleteSet(dest, setName); err != nil {
+			return err
+		}
+	}
+	return nil
+}
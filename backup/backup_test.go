@@ -1,12 +1,15 @@
-package backup_sets
+package backup
 
 import (
+	"encoding/json"
 	"github.com/stretchr/testify/assert"
+	"github.com/timabell/disk-hog-backup/backup_sets"
 	"github.com/timabell/disk-hog-backup/test_helpers"
 	"io/ioutil"
 	"os"
 	"path/filepath"
 	"testing"
+	"time"
 )
 
 const deepPath = "thats/deep"
@@ -19,13 +22,33 @@ func TestBackup(t *testing.T) {
 	defer os.RemoveAll(dest) // comment this out to be able to inspect what we actually got
 
 	//smoke test
-	setName, err := Backup(source, dest)
+	setName, _, err := Backup(source, dest, Options{})
 	assert.NoError(t, err)
 
 	// Just a quick check that deeply nested file is copied.
 	// All other edge cases are tested in unit tests.
 	_, err = os.Stat(filepath.Join(dest, setName, deepPath,"/testfile.txt"))
 	assert.NoError(t, err)
+
+	complete, err := backup_sets.IsComplete(filepath.Join(dest, setName))
+	assert.NoError(t, err)
+	assert.True(t, complete, "finished set shouldn't be marked incomplete")
+}
+
+func TestChangedSinceMarksSetPartial(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	options := Options{}
+	options.Copy.ChangedSince = time.Now().Add(-time.Hour)
+
+	setName, _, err := Backup(source, dest, options)
+	assert.NoError(t, err)
+
+	partial, err := backup_sets.IsPartial(filepath.Join(dest, setName))
+	assert.NoError(t, err)
+	assert.True(t, partial, "a top-up set shouldn't look like a full backup")
 }
 
 func TestBackupNonExistentPath(t *testing.T) {
@@ -40,12 +63,45 @@ func TestCreatesDestinationFolder(t *testing.T) {
 
 	nonExistentDestination := filepath.Join(dest, "to-be-created")
 
-	Backup(source, nonExistentDestination)
+	Backup(source, nonExistentDestination, Options{})
 
 	_, err := ioutil.ReadDir(nonExistentDestination)
 	assert.NoError(t, err, "destination folder should be copied")
 }
 
+func TestRemoveIncomplete(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	started := time.Date(2001, 1, 1, 0, 0, 0, 0, time.Local)
+	crashed, err := backup_sets.CreateEmptySet(dest, func() time.Time { return started })
+	assert.NoError(t, err)
+	markAbandoned(filepath.Join(dest, crashed), started)
+	running, err := backup_sets.CreateEmptySet(dest, func() time.Time { return started.Add(time.Hour) })
+	assert.NoError(t, err)
+
+	_, _, err = Backup(source, dest, Options{RemoveIncomplete: true})
+	assert.NoError(t, err)
+
+	_, err = os.Stat(filepath.Join(dest, crashed))
+	assert.True(t, os.IsNotExist(err), "incomplete set should have been removed")
+	_, err = os.Stat(filepath.Join(dest, running))
+	assert.NoError(t, err, "set still being written by a live process should be left alone")
+}
+
+// markAbandoned makes an incomplete set look like it was left by a run on
+// another machine that's long gone.
+func markAbandoned(setFolder string, started time.Time) {
+	marker, err := json.Marshal(backup_sets.RunInfo{PID: 1234, Host: "another-machine", Started: started})
+	if err != nil {
+		panic(err)
+	}
+	if err := ioutil.WriteFile(filepath.Join(setFolder, backup_sets.IncompleteMarkerName), marker, os.ModePerm); err != nil {
+		panic(err)
+	}
+}
+
 func createSource() (source string) {
 	source = test_helpers.CreateTmpFolder("orig")
 
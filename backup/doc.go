@@ -0,0 +1,4 @@
+// Package backup runs a backup of a source folder into a new set in a
+// destination folder. It is what the disk-hog-backup command runs by default,
+// and can be used directly by other Go programs that want to embed backups.
+package backup
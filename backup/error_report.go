@@ -0,0 +1,21 @@
+package backup
+
+import (
+	"github.com/timabell/disk-hog-backup/dherrors"
+	"io/ioutil"
+	"strings"
+)
+
+func writeErrorReport(path string, errs []error) error {
+	return dherrors.Wrap(dherrors.OpWrite, path, ioutil.WriteFile(path, []byte(errorReport(errs)), 0666))
+}
+
+// errorReport lists errors one per line.
+func errorReport(errs []error) string {
+	var report strings.Builder
+	for _, err := range errs {
+		report.WriteString(err.Error())
+		report.WriteString("\n")
+	}
+	return report.String()
+}
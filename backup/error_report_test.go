@@ -0,0 +1,25 @@
+package backup
+
+import (
+	"errors"
+	"github.com/stretchr/testify/assert"
+	"github.com/timabell/disk-hog-backup/backup_sets"
+	"github.com/timabell/disk-hog-backup/test_helpers"
+	"io/ioutil"
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestWriteErrorReport(t *testing.T) {
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	reportPath := filepath.Join(dest, backup_sets.ErrorReportName)
+
+	err := writeErrorReport(reportPath, []error{errors.New("read failed on a"), errors.New("read failed on b")})
+	assert.NoError(t, err)
+
+	contents, err := ioutil.ReadFile(reportPath)
+	assert.NoError(t, err)
+	assert.Equal(t, "read failed on a\nread failed on b\n", string(contents))
+}
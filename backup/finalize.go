@@ -0,0 +1,84 @@
+package backup
+
+import (
+	"errors"
+	"fmt"
+	"github.com/timabell/disk-hog-backup/backup_sets"
+	"github.com/timabell/disk-hog-backup/dhcopy"
+	"path/filepath"
+)
+
+// finalizeSpaceMargin is the free space needed on the destination to finalize
+// a set on top of the error report and special files list, covering the set
+// info and leaving the filesystem some room.
+const finalizeSpaceMargin = 1024 * 1024
+
+// FinalizeError is returned when everything was copied into a set but its
+// own files couldn't be written afterwards, typically because the destination
+// filled up right at the end. The data is there but the set is left marked
+// incomplete.
+type FinalizeError struct {
+	Err error
+}
+
+func (e *FinalizeError) Error() string {
+	return "finalizing set failed: " + e.Err.Error()
+}
+
+func (e *FinalizeError) Unwrap() error {
+	return e.Err
+}
+
+// IsFinalizeError reports whether err, or anything it wraps, is a
+// FinalizeError.
+func IsFinalizeError(err error) bool {
+	var finalizeErr *FinalizeError
+	return errors.As(err, &finalizeErr)
+}
+
+// finalize writes the error report and list of special files, if there's
+// anything to go in them, and the set info, then marks the set complete.
+// partial says why the set only holds some of the source, if it does.
+// Nothing is written if the destination doesn't have the space for it.
+func finalize(setFolder string, partial string, stats *dhcopy.Stats) error {
+	if err := checkFinalizeSpace(setFolder, stats); err != nil {
+		return &FinalizeError{err}
+	}
+	if len(stats.Errors) > 0 {
+		err := writeErrorReport(filepath.Join(setFolder, backup_sets.ErrorReportName), stats.Errors)
+		if err != nil {
+			return &FinalizeError{err}
+		}
+	}
+	if len(stats.SpecialFiles) > 0 {
+		err := writeSpecialFiles(filepath.Join(setFolder, backup_sets.SpecialFilesName), stats.SpecialFiles)
+		if err != nil {
+			return &FinalizeError{err}
+		}
+	}
+	info := backup_sets.SetInfo{SizeBytes: stats.BytesCopied + stats.DedupedBytes, Partial: partial}
+	if err := backup_sets.WriteSetInfo(setFolder, info); err != nil {
+		return &FinalizeError{err}
+	}
+	if err := backup_sets.MarkComplete(setFolder); err != nil {
+		return &FinalizeError{err}
+	}
+	return nil
+}
+
+// checkFinalizeSpace fails if there isn't enough free space on the
+// destination to write the set's own files.
+func checkFinalizeSpace(setFolder string, stats *dhcopy.Stats) error {
+	free, known, err := freeSpace(setFolder)
+	if err != nil {
+		return fmt.Errorf("checking free space on %v: %w", setFolder, err)
+	}
+	if !known {
+		return nil
+	}
+	needed := int64(len(errorReport(stats.Errors))+len(specialFilesList(stats.SpecialFiles))) + finalizeSpaceMargin
+	if free < needed {
+		return fmt.Errorf("only %v free on the destination, at least %v is needed to finalize the set", dhcopy.FormatBytes(free), dhcopy.FormatBytes(needed))
+	}
+	return nil
+}
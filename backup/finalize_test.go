@@ -0,0 +1,35 @@
+package backup
+
+import (
+	"github.com/stretchr/testify/assert"
+	"github.com/timabell/disk-hog-backup/dhcopy"
+	"github.com/timabell/disk-hog-backup/test_helpers"
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestFinalizeFailureIsDistinguishable(t *testing.T) {
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+
+	// no incomplete marker to remove, as if it had been lost
+	err := finalize(filepath.Join(dest, "dhb-set-20010101-000000"), "", &dhcopy.Stats{})
+
+	assert.Error(t, err)
+	assert.True(t, IsFinalizeError(err))
+	assert.False(t, IsFinalizeError(os.ErrNotExist))
+}
+
+func TestFinalizeChecksFreeSpace(t *testing.T) {
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+
+	assert.NoError(t, checkFinalizeSpace(dest, &dhcopy.Stats{}))
+
+	free, known, err := freeSpace(dest)
+	assert.NoError(t, err)
+	if known {
+		assert.True(t, free > 0)
+	}
+}
@@ -0,0 +1,15 @@
+// +build linux darwin freebsd
+
+package backup
+
+import "syscall"
+
+// freeSpace returns the bytes available to this user on the filesystem
+// holding path.
+func freeSpace(path string) (int64, bool, error) {
+	var stat syscall.Statfs_t
+	if err := syscall.Statfs(path, &stat); err != nil {
+		return 0, false, err
+	}
+	return int64(stat.Bavail) * int64(stat.Bsize), true, nil
+}
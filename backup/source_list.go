@@ -0,0 +1,46 @@
+package backup
+
+import (
+	"io"
+	"io/ioutil"
+	"os"
+	"strings"
+)
+
+// ReadSourceList reads a file of paths to back up, in the style of rsync's
+// --files-from, or standard input if listPath is "-". Paths are one per line,
+// or separated by NUL characters (as from find -print0) if nulDelimited.
+// Blank entries are ignored.
+func ReadSourceList(listPath string, nulDelimited bool) ([]string, error) {
+	if listPath == "-" {
+		return parseSourceList(os.Stdin, nulDelimited)
+	}
+	file, err := os.Open(listPath)
+	if err != nil {
+		return nil, err
+	}
+	defer file.Close()
+	return parseSourceList(file, nulDelimited)
+}
+
+func parseSourceList(reader io.Reader, nulDelimited bool) ([]string, error) {
+	contents, err := ioutil.ReadAll(reader)
+	if err != nil {
+		return nil, err
+	}
+	separator := "\n"
+	if nulDelimited {
+		separator = "\x00"
+	}
+	var paths []string
+	for _, entry := range strings.Split(string(contents), separator) {
+		if !nulDelimited {
+			entry = strings.TrimRight(entry, "\r")
+		}
+		if entry == "" {
+			continue
+		}
+		paths = append(paths, entry)
+	}
+	return paths, nil
+}
@@ -0,0 +1,96 @@
+package backup
+
+import (
+	"github.com/stretchr/testify/assert"
+	"github.com/timabell/disk-hog-backup/test_helpers"
+	"io/ioutil"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+func TestReadSourceList(t *testing.T) {
+	folder := test_helpers.CreateTmpFolder("list")
+	defer os.RemoveAll(folder)
+	listPath := filepath.Join(folder, "sources.txt")
+	if err := ioutil.WriteFile(listPath, []byte("photos\r\n\ndocs/letter.txt\n"), os.ModePerm); err != nil {
+		panic(err)
+	}
+
+	paths, err := ReadSourceList(listPath, false)
+
+	assert.NoError(t, err)
+	assert.Equal(t, []string{"photos", "docs/letter.txt"}, paths)
+}
+
+func TestParseNulDelimitedSourceList(t *testing.T) {
+	paths, err := parseSourceList(strings.NewReader("photos\x00docs/new\nline.txt\x00"), true)
+
+	assert.NoError(t, err)
+	assert.Equal(t, []string{"photos", "docs/new\nline.txt"}, paths)
+}
+
+func TestReadEmptySourceListFromStdin(t *testing.T) {
+	folder := test_helpers.CreateTmpFolder("list")
+	defer os.RemoveAll(folder)
+	emptyInput := filepath.Join(folder, "stdin")
+	if err := ioutil.WriteFile(emptyInput, nil, os.ModePerm); err != nil {
+		panic(err)
+	}
+	stdin, err := os.Open(emptyInput)
+	if err != nil {
+		panic(err)
+	}
+	defer stdin.Close()
+	originalStdin := os.Stdin
+	os.Stdin = stdin
+	defer func() { os.Stdin = originalStdin }()
+
+	paths, err := ReadSourceList("-", false)
+
+	assert.NoError(t, err)
+	assert.Empty(t, paths)
+}
+
+func TestParseEmptyNulDelimitedSourceList(t *testing.T) {
+	for _, input := range []string{"", "\x00", "\x00\x00"} {
+		paths, err := parseSourceList(strings.NewReader(input), true)
+
+		assert.NoError(t, err)
+		assert.Empty(t, paths, "%q should be an empty list", input)
+	}
+}
+
+func TestBackupSourceList(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	if err := ioutil.WriteFile(filepath.Join(source, "unlisted.txt"), []byte("nope"), os.ModePerm); err != nil {
+		panic(err)
+	}
+
+	setName, _, err := Backup(source, dest, Options{SourceList: []string{"thats"}, SourceListGiven: true})
+	assert.NoError(t, err)
+
+	_, err = os.Stat(filepath.Join(dest, setName, deepPath, "testfile.txt"))
+	assert.NoError(t, err, "listed folder should be backed up")
+	_, err = os.Stat(filepath.Join(dest, setName, "unlisted.txt"))
+	assert.True(t, os.IsNotExist(err), "unlisted file should not be backed up")
+}
+
+func TestBackupEmptySourceList(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+
+	setName, _, err := Backup(source, dest, Options{SourceListGiven: true})
+	assert.NoError(t, err)
+	assert.Empty(t, setName)
+
+	contents, err := ioutil.ReadDir(dest)
+	assert.NoError(t, err)
+	assert.Empty(t, contents, "nothing should be backed up for an empty list")
+}
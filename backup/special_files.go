@@ -0,0 +1,28 @@
+package backup
+
+import (
+	"fmt"
+	"github.com/timabell/disk-hog-backup/dhcopy"
+	"github.com/timabell/disk-hog-backup/dherrors"
+	"io/ioutil"
+	"strings"
+)
+
+func writeSpecialFiles(path string, files []dhcopy.SpecialFile) error {
+	return dherrors.Wrap(dherrors.OpWrite, path, ioutil.WriteFile(path, []byte(specialFilesList(files)), 0666))
+}
+
+// specialFilesList lists special files one per line as tab separated type,
+// permissions in octal, major:minor device numbers ("-" if not a device or
+// unknown) and path.
+func specialFilesList(files []dhcopy.SpecialFile) string {
+	var list strings.Builder
+	for _, file := range files {
+		device := "-"
+		if file.Type == "char" || file.Type == "block" {
+			device = fmt.Sprintf("%v:%v", file.Major, file.Minor)
+		}
+		fmt.Fprintf(&list, "%v\t%04o\t%v\t%v\n", file.Type, uint32(file.Mode.Perm()), device, file.Path)
+	}
+	return list.String()
+}
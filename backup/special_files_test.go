@@ -0,0 +1,28 @@
+package backup
+
+import (
+	"github.com/stretchr/testify/assert"
+	"github.com/timabell/disk-hog-backup/backup_sets"
+	"github.com/timabell/disk-hog-backup/dhcopy"
+	"github.com/timabell/disk-hog-backup/test_helpers"
+	"io/ioutil"
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestWriteSpecialFiles(t *testing.T) {
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	listPath := filepath.Join(dest, backup_sets.SpecialFilesName)
+
+	err := writeSpecialFiles(listPath, []dhcopy.SpecialFile{
+		{Path: "run/pipe", Type: "fifo", Mode: 0600},
+		{Path: "dev/null", Type: "char", Mode: 0666, Major: 1, Minor: 3},
+	})
+	assert.NoError(t, err)
+
+	contents, err := ioutil.ReadFile(listPath)
+	assert.NoError(t, err)
+	assert.Equal(t, "fifo\t0600\t-\trun/pipe\nchar\t0666\t1:3\tdev/null\n", string(contents))
+}
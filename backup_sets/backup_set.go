@@ -1,14 +1,48 @@
 package backup_sets
 
 import (
+	"encoding/json"
+	"fmt"
+	"github.com/timabell/disk-hog-backup/dherrors"
+	"io/ioutil"
 	"os"
 	"path/filepath"
 	"time"
 )
 
+// CreateEmptySet creates a new set folder in dest, marked as incomplete until
+// MarkComplete is called. It fails if a set with the same name already
+// exists, e.g. from another backup started in the same second, rather than
+// writing into it.
 func CreateEmptySet(dest string, getTime func() time.Time) (setName string, err error) {
 	setName = GenerateName(getTime)
 	destFolder := filepath.Join(dest, setName)
-	err = os.MkdirAll(destFolder, os.ModePerm)
+	err = os.Mkdir(destFolder, os.ModePerm)
+	if os.IsExist(err) {
+		return "", fmt.Errorf("backup set %v already exists", destFolder)
+	}
+	err = dherrors.Wrap(dherrors.OpWrite, destFolder, err)
+	if err != nil {
+		return "", err
+	}
+	markerPath := filepath.Join(destFolder, IncompleteMarkerName)
+	marker, err := json.Marshal(currentRun(getTime()))
+	if err != nil {
+		return "", err
+	}
+	err = dherrors.Wrap(dherrors.OpWrite, markerPath, ioutil.WriteFile(markerPath, append(marker, '\n'), 0644))
 	return
 }
+
+// MarkComplete removes the incomplete marker from a set once everything has
+// been written into it.
+func MarkComplete(setFolder string) error {
+	markerPath := filepath.Join(setFolder, IncompleteMarkerName)
+	return dherrors.Wrap(dherrors.OpWrite, markerPath, os.Remove(markerPath))
+}
+
+// DeleteSet removes a set and everything in it from dest.
+func DeleteSet(dest string, setName string) error {
+	setFolder := filepath.Join(dest, setName)
+	return dherrors.Wrap(dherrors.OpWrite, setFolder, os.RemoveAll(setFolder))
+}
@@ -28,3 +28,31 @@ func TestCreation(t *testing.T) {
 	_, err = ioutil.ReadDir(dirPath)
 	assert.NoError(t, err, "set folder should be copied")
 }
+
+func TestCreationFailsIfSetExists(t *testing.T) {
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	timeFixer := test_helpers.TimeFixer()
+	setName, err := CreateEmptySet(dest, timeFixer)
+	assert.NoError(t, err)
+	assert.NoError(t, MarkComplete(filepath.Join(dest, setName)))
+
+	_, err = CreateEmptySet(dest, timeFixer)
+
+	assert.Error(t, err)
+	complete, err := IsComplete(filepath.Join(dest, setName))
+	assert.NoError(t, err)
+	assert.True(t, complete, "the existing set should not be marked incomplete again")
+}
+
+func TestDeleteSet(t *testing.T) {
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	setName, err := CreateEmptySet(dest, test_helpers.TimeFixer())
+	assert.NoError(t, err)
+
+	assert.NoError(t, DeleteSet(dest, setName))
+
+	_, err = os.Stat(filepath.Join(dest, setName))
+	assert.True(t, os.IsNotExist(err), "set folder should be gone")
+}
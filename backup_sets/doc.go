@@ -0,0 +1,4 @@
+// Package backup_sets manages the sets in a destination folder: naming and
+// creating them, marking them complete, listing them and their metadata
+// files.
+package backup_sets
@@ -0,0 +1,85 @@
+package backup_sets
+
+import (
+	"github.com/timabell/disk-hog-backup/dherrors"
+	"io/ioutil"
+	"os"
+	"path/filepath"
+	"strings"
+)
+
+// ErrorReportName is the file written into a set listing anything that
+// couldn't be backed up when carrying on after errors.
+const ErrorReportName = "dhb-errors.txt"
+
+// SpecialFilesName is the file written into a set listing the FIFOs, sockets
+// and device nodes in the source, when asked to record them.
+const SpecialFilesName = "dhb-special-files.txt"
+
+// IncompleteMarkerName is written into a set when it's created and removed
+// once the backup has finished, so a set left behind by a crashed or killed
+// run can be told apart from a finished one.
+const IncompleteMarkerName = "dhb-incomplete"
+
+// DedupeTempName is the temporary hardlink made in a set while dedupe is
+// replacing one of its files, which is only left behind if that's
+// interrupted.
+const DedupeTempName = "dhb-dedupe-temp"
+
+// CaseProbeName is created briefly in a new set to find out whether the
+// destination ignores the case of names, and only left behind if the run is
+// killed at that moment.
+const CaseProbeName = "dhb-case-probe"
+
+// metadataFiles are written into the root of a set by disk-hog-backup itself
+// and aren't part of the backed up data.
+var metadataFiles = []string{
+	ErrorReportName,
+	SpecialFilesName,
+	IncompleteMarkerName,
+	SetInfoName,
+	DedupeTempName,
+	CaseProbeName,
+}
+
+// IsSetFolder reports whether a folder name is that of a backup set.
+func IsSetFolder(name string) bool {
+	return strings.HasPrefix(name, setPrefix)
+}
+
+// IsMetadataFile reports whether a file name at the root of a set is one of
+// disk-hog-backup's own files rather than backed up data.
+func IsMetadataFile(name string) bool {
+	for _, metadataFile := range metadataFiles {
+		if name == metadataFile {
+			return true
+		}
+	}
+	return false
+}
+
+// ErrorCount returns how many errors were recorded in a set's error report.
+func ErrorCount(setFolder string) (int, error) {
+	reportPath := filepath.Join(setFolder, ErrorReportName)
+	contents, err := ioutil.ReadFile(reportPath)
+	if os.IsNotExist(err) {
+		return 0, nil
+	}
+	if err != nil {
+		return 0, dherrors.Wrap(dherrors.OpRead, reportPath, err)
+	}
+	return strings.Count(string(contents), "\n"), nil
+}
+
+// IsComplete reports whether the backup into a set finished.
+func IsComplete(setFolder string) (bool, error) {
+	markerPath := filepath.Join(setFolder, IncompleteMarkerName)
+	_, err := os.Lstat(markerPath)
+	if os.IsNotExist(err) {
+		return true, nil
+	}
+	if err != nil {
+		return false, dherrors.Wrap(dherrors.OpRead, markerPath, err)
+	}
+	return false, nil
+}
@@ -0,0 +1,36 @@
+package backup_sets
+
+import (
+	"github.com/stretchr/testify/assert"
+	"github.com/timabell/disk-hog-backup/test_helpers"
+	"io/ioutil"
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestIsSetFolder(t *testing.T) {
+	assert.True(t, IsSetFolder(GenerateName(test_helpers.TimeFixer())))
+	assert.False(t, IsSetFolder("holiday-photos"))
+}
+
+func TestIsMetadataFile(t *testing.T) {
+	assert.True(t, IsMetadataFile(ErrorReportName))
+	assert.False(t, IsMetadataFile("errors.txt"))
+}
+
+func TestErrorCount(t *testing.T) {
+	set := test_helpers.CreateTmpFolder("set")
+	defer os.RemoveAll(set)
+
+	count, err := ErrorCount(set)
+	assert.NoError(t, err)
+	assert.Equal(t, 0, count, "no report means no errors")
+
+	if err := ioutil.WriteFile(filepath.Join(set, ErrorReportName), []byte("read failed on a\nread failed on b\n"), os.ModePerm); err != nil {
+		panic(err)
+	}
+	count, err = ErrorCount(set)
+	assert.NoError(t, err)
+	assert.Equal(t, 2, count)
+}
@@ -0,0 +1,12 @@
+// +build !windows
+
+package backup_sets
+
+import "syscall"
+
+// processExists reports whether there's a process with the given ID. One
+// owned by another user still counts.
+func processExists(pid int) bool {
+	err := syscall.Kill(pid, 0)
+	return err == nil || err == syscall.EPERM
+}
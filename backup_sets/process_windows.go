@@ -0,0 +1,13 @@
+package backup_sets
+
+import "os"
+
+// processExists reports whether there's a process with the given ID.
+func processExists(pid int) bool {
+	process, err := os.FindProcess(pid)
+	if err != nil {
+		return false
+	}
+	process.Release()
+	return true
+}
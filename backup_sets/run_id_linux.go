@@ -0,0 +1,36 @@
+package backup_sets
+
+import (
+	"fmt"
+	"io/ioutil"
+	"strings"
+)
+
+// bootID returns an ID that changes every time the machine starts, so that a
+// process ID recorded before a restart isn't mistaken for a process that's
+// running now.
+func bootID() string {
+	contents, err := ioutil.ReadFile("/proc/sys/kernel/random/boot_id")
+	if err != nil {
+		return ""
+	}
+	return strings.TrimSpace(string(contents))
+}
+
+// processStart returns when a process started, in clock ticks since boot, so
+// a process ID that's been reused since can be told apart from the original.
+// It's "" if the process doesn't exist.
+func processStart(pid int) string {
+	contents, err := ioutil.ReadFile(fmt.Sprintf("/proc/%v/stat", pid))
+	if err != nil {
+		return ""
+	}
+	// the command name in field 2 is in brackets and can contain spaces, so
+	// count from after it, where field 3 is first
+	stat := string(contents)
+	fields := strings.Fields(stat[strings.LastIndexByte(stat, ')')+1:])
+	if len(fields) < 20 {
+		return ""
+	}
+	return fields[22-3]
+}
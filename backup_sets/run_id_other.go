@@ -0,0 +1,14 @@
+// +build !linux
+
+package backup_sets
+
+// bootID returns "" as there's no boot ID to read on this platform.
+func bootID() string {
+	return ""
+}
+
+// processStart returns "" as process start times aren't read on this
+// platform.
+func processStart(pid int) string {
+	return ""
+}
@@ -0,0 +1,92 @@
+package backup_sets
+
+import (
+	"encoding/json"
+	"github.com/timabell/disk-hog-backup/dherrors"
+	"io/ioutil"
+	"os"
+	"path/filepath"
+	"time"
+)
+
+// RunInfo says which backup run is writing into a set. It's recorded in the
+// incomplete marker so that sets still being written can be told apart from
+// those left behind by crashed runs.
+type RunInfo struct {
+	PID     int       `json:"pid"`
+	Host    string    `json:"host"`
+	Started time.Time `json:"started"`
+
+	// BootID and ProcessStart identify when the machine and the process
+	// were started, where the platform has them, to tell whether PID has
+	// been reused since.
+	BootID       string `json:"boot_id,omitempty"`
+	ProcessStart string `json:"process_start,omitempty"`
+}
+
+// remoteRunTimeout is how long a set being written from another machine, or
+// by a version that didn't record RunInfo, is assumed to still be in
+// progress, as there's no way of checking whether its process is still
+// running. It also applies to runs on this machine that didn't record when
+// they started, as their process ID may have been reused.
+const remoteRunTimeout = 24 * time.Hour
+
+func currentRun(started time.Time) RunInfo {
+	host, _ := os.Hostname()
+	pid := os.Getpid()
+	return RunInfo{PID: pid, Host: host, Started: started, BootID: bootID(), ProcessStart: processStart(pid)}
+}
+
+// ReadRunInfo reads which run is writing into an incomplete set. found is
+// false if the marker doesn't record it.
+func ReadRunInfo(setFolder string) (info RunInfo, found bool, err error) {
+	markerPath := filepath.Join(setFolder, IncompleteMarkerName)
+	contents, err := ioutil.ReadFile(markerPath)
+	if err != nil {
+		return info, false, dherrors.Wrap(dherrors.OpRead, markerPath, err)
+	}
+	if len(contents) == 0 {
+		return info, false, nil
+	}
+	if err := json.Unmarshal(contents, &info); err != nil {
+		return info, false, dherrors.Wrap(dherrors.OpRead, markerPath, err)
+	}
+	return info, true, nil
+}
+
+// MayBeRunning reports whether the backup writing into an incomplete set
+// could still be going, in which case the set mustn't be deleted. Runs on
+// this machine are checked by their process ID, as long as it's still the same
+// process, which is assumed until remoteRunTimeout where that can't be told.
+// Runs elsewhere can't be checked, so they're assumed to be
+// going until remoteRunTimeout after they started.
+func MayBeRunning(setFolder string, now time.Time) (bool, error) {
+	info, found, err := ReadRunInfo(setFolder)
+	if err != nil {
+		return false, err
+	}
+	if !found {
+		markerPath := filepath.Join(setFolder, IncompleteMarkerName)
+		marker, err := os.Stat(markerPath)
+		if err != nil {
+			return false, dherrors.Wrap(dherrors.OpRead, markerPath, err)
+		}
+		return now.Sub(marker.ModTime()) < remoteRunTimeout, nil
+	}
+	if host, _ := os.Hostname(); info.Host == host {
+		if !processExists(info.PID) {
+			return false, nil
+		}
+		current := bootID()
+		if info.BootID != "" && current != "" && info.BootID != current {
+			return false, nil // restarted since
+		}
+		if info.BootID != "" && info.ProcessStart != "" {
+			if start := processStart(info.PID); start != "" {
+				return start == info.ProcessStart, nil
+			}
+		}
+		return now.Sub(info.Started) < remoteRunTimeout, nil
+	}
+	return now.Sub(info.Started) < remoteRunTimeout, nil
+}
@@ -0,0 +1,61 @@
+package backup_sets
+
+import (
+	"encoding/json"
+	"github.com/stretchr/testify/assert"
+	"github.com/timabell/disk-hog-backup/test_helpers"
+	"io/ioutil"
+	"os"
+	"path/filepath"
+	"testing"
+	"time"
+)
+
+func TestMayBeRunning(t *testing.T) {
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	now := time.Now()
+	setName, err := CreateEmptySet(dest, func() time.Time { return now })
+	assert.NoError(t, err)
+	setFolder := filepath.Join(dest, setName)
+
+	running, err := MayBeRunning(setFolder, now.Add(time.Hour))
+	assert.NoError(t, err)
+	assert.True(t, running, "a set being written by a live process on this machine is running")
+	running, err = MayBeRunning(setFolder, now.Add(48*time.Hour))
+	assert.NoError(t, err)
+	if bootID() != "" && processStart(os.Getpid()) != "" {
+		assert.True(t, running, "a set being written by a live process on this machine is running however old")
+
+		host, _ := os.Hostname()
+		writeRunInfo(setFolder, RunInfo{PID: os.Getpid(), Host: host, Started: now, BootID: "before-a-restart", ProcessStart: processStart(os.Getpid())})
+		running, err = MayBeRunning(setFolder, now.Add(time.Hour))
+		assert.NoError(t, err)
+		assert.False(t, running, "a process ID from before a restart could belong to anything")
+
+		writeRunInfo(setFolder, RunInfo{PID: os.Getpid(), Host: host, Started: now, BootID: bootID(), ProcessStart: "1"})
+		running, err = MayBeRunning(setFolder, now.Add(time.Hour))
+		assert.NoError(t, err)
+		assert.False(t, running, "a process ID reused by a different process isn't the run")
+	} else {
+		assert.False(t, running, "a run that can't be told apart from a reused process ID is only trusted for so long")
+	}
+
+	writeRunInfo(setFolder, RunInfo{PID: 1234, Host: "another-machine", Started: now})
+	running, err = MayBeRunning(setFolder, now.Add(time.Hour))
+	assert.NoError(t, err)
+	assert.True(t, running, "a recent run on another machine may still be going")
+	running, err = MayBeRunning(setFolder, now.Add(48*time.Hour))
+	assert.NoError(t, err)
+	assert.False(t, running, "an old run on another machine is assumed to have died")
+}
+
+func writeRunInfo(setFolder string, info RunInfo) {
+	contents, err := json.Marshal(info)
+	if err != nil {
+		panic(err)
+	}
+	if err := ioutil.WriteFile(filepath.Join(setFolder, IncompleteMarkerName), contents, os.ModePerm); err != nil {
+		panic(err)
+	}
+}
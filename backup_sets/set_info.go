@@ -0,0 +1,57 @@
+package backup_sets
+
+import (
+	"encoding/json"
+	"github.com/timabell/disk-hog-backup/dherrors"
+	"io/ioutil"
+	"os"
+	"path/filepath"
+)
+
+// SetInfoName is written into a set as it's finalized, recording details of
+// the set that would otherwise mean reading through all of it.
+const SetInfoName = "dhb-set-info.json"
+
+// SetInfo is what's recorded in SetInfoName.
+type SetInfo struct {
+	// SizeBytes is the total size of the files backed up into the set,
+	// counting hardlinked duplicates each time they appear.
+	SizeBytes int64 `json:"size_bytes"`
+
+	// Partial says why the set only holds some of the source, like a
+	// -changed-since top-up. Empty for full backups.
+	Partial string `json:"partial,omitempty"`
+}
+
+// WriteSetInfo records info about a set in it.
+func WriteSetInfo(setFolder string, info SetInfo) error {
+	infoPath := filepath.Join(setFolder, SetInfoName)
+	contents, err := json.Marshal(info)
+	if err != nil {
+		return err
+	}
+	return dherrors.Wrap(dherrors.OpWrite, infoPath, ioutil.WriteFile(infoPath, append(contents, '\n'), 0644))
+}
+
+// IsPartial reports whether a finished set only holds some of the source.
+func IsPartial(setFolder string) (bool, error) {
+	info, _, err := ReadSetInfo(setFolder)
+	return info.Partial != "", err
+}
+
+// ReadSetInfo reads what was recorded about a set when it was finalized.
+// found is false for sets made before this was recorded.
+func ReadSetInfo(setFolder string) (info SetInfo, found bool, err error) {
+	infoPath := filepath.Join(setFolder, SetInfoName)
+	contents, err := ioutil.ReadFile(infoPath)
+	if os.IsNotExist(err) {
+		return info, false, nil
+	}
+	if err != nil {
+		return info, false, dherrors.Wrap(dherrors.OpRead, infoPath, err)
+	}
+	if err := json.Unmarshal(contents, &info); err != nil {
+		return info, false, dherrors.Wrap(dherrors.OpRead, infoPath, err)
+	}
+	return info, true, nil
+}
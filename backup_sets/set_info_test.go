@@ -0,0 +1,23 @@
+package backup_sets
+
+import (
+	"github.com/stretchr/testify/assert"
+	"github.com/timabell/disk-hog-backup/test_helpers"
+	"os"
+	"testing"
+)
+
+func TestSetInfo(t *testing.T) {
+	set := test_helpers.CreateTmpFolder("set")
+	defer os.RemoveAll(set)
+
+	_, found, err := ReadSetInfo(set)
+	assert.NoError(t, err)
+	assert.False(t, found, "older sets have no info")
+
+	assert.NoError(t, WriteSetInfo(set, SetInfo{SizeBytes: 1234}))
+	info, found, err := ReadSetInfo(set)
+	assert.NoError(t, err)
+	assert.True(t, found)
+	assert.Equal(t, SetInfo{SizeBytes: 1234}, info)
+}
@@ -0,0 +1,114 @@
+package backup_sets
+
+import (
+	"fmt"
+	"github.com/timabell/disk-hog-backup/dherrors"
+	"io/ioutil"
+	"path/filepath"
+	"sort"
+	"strings"
+	"time"
+)
+
+// the timestamp part of set names, as generated by GenerateName
+const setTimeFormat = "20060102-150405"
+
+// ParseSetTime returns when a set was created, from its name.
+func ParseSetTime(name string) (time.Time, error) {
+	if !IsSetFolder(name) {
+		return time.Time{}, fmt.Errorf("%v is not a backup set name", name)
+	}
+	return time.ParseInLocation(setTimeFormat, strings.TrimPrefix(name, setPrefix), time.Local)
+}
+
+// ListSets returns the names of the finished backup sets in dest, oldest
+// first, including partial ones. Sets left incomplete by a crashed run are
+// left out.
+func ListSets(dest string) ([]string, error) {
+	return listSets(dest, func(complete bool) bool { return complete })
+}
+
+// ListIncompleteSets returns the names of the sets in dest that were never
+// finished, oldest first.
+func ListIncompleteSets(dest string) ([]string, error) {
+	return listSets(dest, func(complete bool) bool { return !complete })
+}
+
+// ListAllSets returns the names of all the sets in dest, finished or not,
+// oldest first.
+func ListAllSets(dest string) ([]string, error) {
+	return listSets(dest, func(complete bool) bool { return true })
+}
+
+// LatestSet returns the name of the newest finished set in dest that's a full
+// backup, rather than a partial one like a -changed-since top-up.
+func LatestSet(dest string) (string, error) {
+	sets, err := ListSets(dest)
+	if err != nil {
+		return "", err
+	}
+	for i := len(sets) - 1; i >= 0; i-- {
+		partial, err := IsPartial(filepath.Join(dest, sets[i]))
+		if err != nil {
+			return "", err
+		}
+		if !partial {
+			return sets[i], nil
+		}
+	}
+	return "", fmt.Errorf("no full backup sets found in %v", dest)
+}
+
+// LatestSetBefore returns the name of the newest finished set in dest created
+// before the given time that's a full backup.
+func LatestSetBefore(dest string, before time.Time) (string, error) {
+	sets, err := ListSets(dest)
+	if err != nil {
+		return "", err
+	}
+	for i := len(sets) - 1; i >= 0; i-- {
+		created, err := ParseSetTime(sets[i])
+		if err != nil {
+			return "", err
+		}
+		if !created.Before(before) {
+			continue
+		}
+		partial, err := IsPartial(filepath.Join(dest, sets[i]))
+		if err != nil {
+			return "", err
+		}
+		if !partial {
+			return sets[i], nil
+		}
+	}
+	return "", fmt.Errorf("no full backup sets in %v from before %v", dest, before.Format("2006-01-02 15:04:05"))
+}
+
+// listSets returns the sets in dest that want says to include, given whether
+// they're complete.
+func listSets(dest string, want func(complete bool) bool) ([]string, error) {
+	contents, err := ioutil.ReadDir(dest)
+	if err != nil {
+		return nil, dherrors.Wrap(dherrors.OpRead, dest, err)
+	}
+	var sets []string
+	for _, item := range contents {
+		if !item.IsDir() {
+			continue
+		}
+		if _, err := ParseSetTime(item.Name()); err != nil {
+			continue
+		}
+		isComplete, err := IsComplete(filepath.Join(dest, item.Name()))
+		if err != nil {
+			return nil, err
+		}
+		if !want(isComplete) {
+			continue
+		}
+		sets = append(sets, item.Name())
+	}
+	sort.Strings(sets) // the timestamp format sorts chronologically
+	return sets, nil
+}
@@ -0,0 +1,103 @@
+package backup_sets
+
+import (
+	"github.com/stretchr/testify/assert"
+	"github.com/timabell/disk-hog-backup/test_helpers"
+	"os"
+	"path/filepath"
+	"testing"
+	"time"
+)
+
+func TestParseSetTime(t *testing.T) {
+	created := time.Date(2001, 2, 3, 14, 5, 6, 0, time.Local)
+	name := GenerateName(func() time.Time { return created })
+
+	parsed, err := ParseSetTime(name)
+
+	assert.NoError(t, err)
+	assert.True(t, created.Equal(parsed))
+}
+
+func TestParseSetTimeRejectsOtherNames(t *testing.T) {
+	_, err := ParseSetTime("holiday-photos")
+	assert.Error(t, err)
+}
+
+func TestListSets(t *testing.T) {
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	for _, name := range []string{"dhb-set-20020101-000000", "dhb-set-20010101-000000", "not-a-set", "dhb-set-garbage"} {
+		if err := os.Mkdir(filepath.Join(dest, name), os.ModePerm); err != nil {
+			panic(err)
+		}
+	}
+
+	sets, err := ListSets(dest)
+
+	assert.NoError(t, err)
+	assert.Equal(t, []string{"dhb-set-20010101-000000", "dhb-set-20020101-000000"}, sets)
+}
+
+func TestListSetsLeavesOutIncompleteSets(t *testing.T) {
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	if err := os.Mkdir(filepath.Join(dest, "dhb-set-20010101-000000"), os.ModePerm); err != nil {
+		panic(err)
+	}
+	crashed, err := CreateEmptySet(dest, test_helpers.TimeFixer())
+	assert.NoError(t, err)
+
+	sets, err := ListSets(dest)
+	assert.NoError(t, err)
+	assert.Equal(t, []string{"dhb-set-20010101-000000"}, sets)
+
+	incomplete, err := ListIncompleteSets(dest)
+	assert.NoError(t, err)
+	assert.Equal(t, []string{crashed}, incomplete)
+
+	assert.NoError(t, MarkComplete(filepath.Join(dest, crashed)))
+	sets, err = ListSets(dest)
+	assert.NoError(t, err)
+	assert.Equal(t, []string{"dhb-set-20010101-000000", crashed}, sets)
+}
+
+func TestLatestSetBefore(t *testing.T) {
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	for _, name := range []string{"dhb-set-20010101-000000", "dhb-set-20010601-000000", "dhb-set-20020101-000000"} {
+		if err := os.Mkdir(filepath.Join(dest, name), os.ModePerm); err != nil {
+			panic(err)
+		}
+	}
+
+	set, err := LatestSetBefore(dest, time.Date(2001, 12, 25, 0, 0, 0, 0, time.Local))
+	assert.NoError(t, err)
+	assert.Equal(t, "dhb-set-20010601-000000", set)
+
+	_, err = LatestSetBefore(dest, time.Date(2000, 1, 1, 0, 0, 0, 0, time.Local))
+	assert.Error(t, err)
+}
+
+func TestLatestSetSkipsPartialSets(t *testing.T) {
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	for _, name := range []string{"dhb-set-20010101-000000", "dhb-set-20010601-000000"} {
+		if err := os.Mkdir(filepath.Join(dest, name), os.ModePerm); err != nil {
+			panic(err)
+		}
+	}
+	assert.NoError(t, WriteSetInfo(filepath.Join(dest, "dhb-set-20010601-000000"), SetInfo{Partial: "only files changed since 2001-05-01 00:00:00"}))
+
+	set, err := LatestSet(dest)
+	assert.NoError(t, err)
+	assert.Equal(t, "dhb-set-20010101-000000", set)
+
+	set, err = LatestSetBefore(dest, time.Date(2002, 1, 1, 0, 0, 0, 0, time.Local))
+	assert.NoError(t, err)
+	assert.Equal(t, "dhb-set-20010101-000000", set)
+
+	sets, err := ListSets(dest)
+	assert.NoError(t, err)
+	assert.Len(t, sets, 2, "partial sets are still listed")
+}
@@ -0,0 +1,66 @@
+package main
+
+import (
+	"flag"
+	"fmt"
+	"github.com/timabell/disk-hog-backup/backup_sets"
+	"github.com/timabell/disk-hog-backup/dhcopy"
+	"github.com/timabell/disk-hog-backup/diff"
+	"log"
+	"os"
+	"path/filepath"
+)
+
+// runCompare lists what has changed in the source since the latest backup,
+// without backing anything up. The options that leave things out of a backup
+// can be given the same as for the backup, so what it skips isn't reported
+// as added.
+func runCompare(args []string) {
+	flags := flag.NewFlagSet("compare", flag.ExitOnError)
+	source := flags.String("source", "", "source folder that is backed up")
+	dest := flags.String("destination", "", "destination folder containing backup sets")
+	asJSON := flags.Bool("json", false, "output the changes as JSON")
+	checksum := flags.Bool("checksum", false, "compare the contents of every file, instead of assuming files with the same size and modification time are unchanged")
+	noSkips := flags.Bool("no-default-skips", false, "as for the backup, include swap files, core dumps, trash and lost+found")
+	setMetadata := flags.Bool("include-set-metadata", false, "as for the backup, include the files of old backup sets in the source")
+	volatilePolicy := flags.String("volatile", "warn", "as for the backup, skip leaves out files that are probably in use")
+	var sizeMin, sizeMax sizeFlag
+	var filterRules filterFlag
+	flags.Var(&sizeMin, "min-size", "as for the backup, leave out files smaller than this")
+	flags.Var(&sizeMax, "max-size", "as for the backup, leave out files bigger than this")
+	flags.Var(&filterRules, "filter", "as for the backup, rsync-style include/exclude rule, can be repeated")
+	flags.Parse(args)
+	if *source == "" || *dest == "" {
+		fmt.Fprintln(flags.Output(), "-source and -destination are required")
+		flags.Usage()
+		os.Exit(2)
+	}
+	volatileFiles, err := dhcopy.ParseVolatilePolicy(*volatilePolicy)
+	if err != nil {
+		log.Fatal(err)
+	}
+	copyOptions := dhcopy.Options{
+		NoDefaultSkips:     *noSkips,
+		IncludeSetMetadata: *setMetadata,
+		Filters:            filterRules,
+		MinSize:            int64(sizeMin),
+		MaxSize:            int64(sizeMax),
+		Volatile:           volatileFiles,
+	}
+	latest, err := backup_sets.LatestSet(*dest)
+	if err != nil {
+		log.Fatal(err)
+	}
+	log.Printf("comparing %v with %v\n", *source, latest)
+	options := diff.Options{
+		Include: func(path string, info os.FileInfo) bool {
+			return copyOptions.Includes(*source, path, info)
+		},
+		Checksum: *checksum,
+	}
+	changes, err := diff.Folders(filepath.Join(*dest, latest), *source, options)
+	if err != nil {
+		log.Fatalf("Compare failed: %s", err)
+	}
+	writeChanges(changes, *asJSON)
+}
@@ -0,0 +1,206 @@
+package dedupe
+
+import (
+	"github.com/timabell/disk-hog-backup/backup_sets"
+	"github.com/timabell/disk-hog-backup/dhcopy"
+	"github.com/timabell/disk-hog-backup/dherrors"
+	"log"
+	"os"
+	"path/filepath"
+	"strings"
+)
+
+// Result counts the duplicate copies that were (or in a dry run, would be)
+// replaced with hardlinks, and the space that frees.
+type Result struct {
+	Files int
+	Bytes int64
+
+	// Unlinked counts files with the same contents as another copy that were
+	// left alone because their modification time or permissions differ, as
+	// linking them would lose what their set recorded.
+	Unlinked int
+}
+
+// attributes are what a file shares with everything hardlinked to it, besides
+// its contents.
+type attributes struct {
+	modTime int64
+	mode    os.FileMode
+}
+
+func attributesOf(info os.FileInfo) attributes {
+	return attributes{info.ModTime().UnixNano(), info.Mode()}
+}
+
+// inode is one stored copy of some contents, and the paths that already link
+// to it.
+type inode struct {
+	info  os.FileInfo
+	paths []string
+}
+
+// Sets finds files with identical contents stored as separate copies in the
+// finished sets in dest, and hardlinks them to the copy in the oldest set.
+// Files with matching hashes are compared byte for byte before being linked,
+// and only linked if their modification time and permissions match too.
+// With dryRun it only reports what it would do.
+func Sets(dest string, dryRun bool) (Result, error) {
+	sets, err := backup_sets.ListSets(dest)
+	if err != nil {
+		return Result{}, err
+	}
+	if !dryRun {
+		if err := removeLeftovers(dest, sets); err != nil {
+			return Result{}, err
+		}
+	}
+	bySize, err := filesBySize(dest, sets)
+	if err != nil {
+		return Result{}, err
+	}
+	var result Result
+	for size, paths := range bySize {
+		var inodes []*inode
+		for _, path := range paths {
+			info, err := os.Lstat(path)
+			if err != nil {
+				return result, dherrors.Wrap(dherrors.OpRead, path, err)
+			}
+			inodes = addPath(inodes, path, info)
+		}
+		if len(inodes) < 2 {
+			continue
+		}
+		byHash := map[string][]*inode{}
+		var hashes []string
+		for _, stored := range inodes {
+			hash, err := dhcopy.HashFile(stored.paths[0])
+			if err != nil {
+				return result, err
+			}
+			key := string(hash)
+			if byHash[key] == nil {
+				hashes = append(hashes, key)
+			}
+			byHash[key] = append(byHash[key], stored)
+		}
+		for _, key := range hashes {
+			copies := byHash[key]
+			first := copies[0].paths[0]
+			originals := map[attributes]string{attributesOf(copies[0].info): first}
+			for _, duplicate := range copies[1:] {
+				same, err := dhcopy.SameContents(first, duplicate.paths[0])
+				if err != nil {
+					return result, err
+				}
+				if !same {
+					log.Printf("%v has the same hash as %v but different contents, leaving it\n", duplicate.paths[0], first)
+					continue
+				}
+				attrs := attributesOf(duplicate.info)
+				original, found := originals[attrs]
+				if !found {
+					log.Printf("%v has the same contents as %v but a different modification time or permissions, leaving it\n", duplicate.paths[0], first)
+					originals[attrs] = duplicate.paths[0]
+					result.Unlinked += len(duplicate.paths)
+					continue
+				}
+				relinked := relinkAll(dest, original, duplicate.paths, dryRun)
+				result.Files += relinked
+				if relinked == len(duplicate.paths) {
+					result.Bytes += size // only freed once nothing links to the copy
+				}
+			}
+		}
+	}
+	return result, nil
+}
+
+// removeLeftovers removes temporary links left in sets by a dedupe that was
+// interrupted.
+func removeLeftovers(dest string, sets []string) error {
+	for _, set := range sets {
+		temp := filepath.Join(dest, set, backup_sets.DedupeTempName)
+		if err := os.Remove(temp); err != nil && !os.IsNotExist(err) {
+			return dherrors.Wrap(dherrors.OpWrite, temp, err)
+		}
+	}
+	return nil
+}
+
+// filesBySize groups the files in sets by size, oldest set first. Empty
+// files aren't worth linking.
+func filesBySize(dest string, sets []string) (map[int64][]string, error) {
+	bySize := map[int64][]string{}
+	for _, set := range sets {
+		setFolder := filepath.Join(dest, set)
+		err := filepath.Walk(setFolder, func(path string, info os.FileInfo, err error) error {
+			if err != nil {
+				return dherrors.Wrap(dherrors.OpRead, path, err)
+			}
+			if !info.Mode().IsRegular() || info.Size() == 0 || (filepath.Dir(path) == setFolder && backup_sets.IsMetadataFile(info.Name())) {
+				return nil
+			}
+			bySize[info.Size()] = append(bySize[info.Size()], path)
+			return nil
+		})
+		if err != nil {
+			return nil, err
+		}
+	}
+	return bySize, nil
+}
+
+// addPath adds path to the inode it's already a hardlink of, or as a new one.
+func addPath(inodes []*inode, path string, info os.FileInfo) []*inode {
+	for _, existing := range inodes {
+		if os.SameFile(existing.info, info) {
+			existing.paths = append(existing.paths, path)
+			return inodes
+		}
+	}
+	return append(inodes, &inode{info: info, paths: []string{path}})
+}
+
+// relinkAll replaces each of paths with a hardlink to original, stopping at
+// the first failure, and returns how many were replaced.
+func relinkAll(dest string, original string, paths []string, dryRun bool) int {
+	for i, path := range paths {
+		if dryRun {
+			log.Printf("would hardlink %v to %v\n", path, original)
+			continue
+		}
+		if err := relink(dest, original, path); err != nil {
+			log.Printf("couldn't hardlink %v to %v, leaving it: %s\n", path, original, err)
+			return i
+		}
+		log.Printf("hardlinked %v to %v\n", path, original)
+	}
+	return len(paths)
+}
+
+// relink replaces path with a hardlink to original, via a temporary link so
+// path is never missing. The temporary link goes in the root of path's set,
+// named as set metadata, so if it's left behind it isn't mistaken for backed
+// up data.
+func relink(dest string, original string, path string) error {
+	temp := filepath.Join(setFolderOf(dest, path), backup_sets.DedupeTempName)
+	if err := os.Link(original, temp); err != nil {
+		return dherrors.Wrap(dherrors.OpLink, temp, err)
+	}
+	if err := os.Rename(temp, path); err != nil {
+		os.Remove(temp)
+		return dherrors.Wrap(dherrors.OpWrite, path, err)
+	}
+	return nil
+}
+
+// setFolderOf returns the folder of the set in dest that path is in.
+func setFolderOf(dest string, path string) string {
+	relPath, err := filepath.Rel(dest, path)
+	if err != nil {
+		return filepath.Dir(path)
+	}
+	return filepath.Join(dest, strings.SplitN(relPath, string(filepath.Separator), 2)[0])
+}
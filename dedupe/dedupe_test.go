@@ -0,0 +1,95 @@
+package dedupe
+
+import (
+	"github.com/stretchr/testify/assert"
+	"github.com/timabell/disk-hog-backup/backup_sets"
+	"github.com/timabell/disk-hog-backup/test_helpers"
+	"io/ioutil"
+	"os"
+	"path/filepath"
+	"testing"
+	"time"
+)
+
+func TestSetsHardlinksDuplicates(t *testing.T) {
+	dest, first, second := createDuplicates()
+	defer os.RemoveAll(dest)
+
+	result, err := Sets(dest, false)
+
+	assert.NoError(t, err)
+	assert.Equal(t, Result{Files: 1, Bytes: int64(len("backmeup susie"))}, result)
+	assert.True(t, test_helpers.SameFile(first, second), "duplicate should be hardlinked to the original")
+	contents, err := ioutil.ReadFile(second)
+	assert.NoError(t, err)
+	assert.Equal(t, "backmeup susie", string(contents))
+
+	result, err = Sets(dest, false)
+	assert.NoError(t, err)
+	assert.Equal(t, Result{}, result, "nothing left to link")
+}
+
+func TestSetsDryRun(t *testing.T) {
+	dest, first, second := createDuplicates()
+	defer os.RemoveAll(dest)
+
+	result, err := Sets(dest, true)
+
+	assert.NoError(t, err)
+	assert.Equal(t, 1, result.Files)
+	assert.False(t, test_helpers.SameFile(first, second), "dry run shouldn't change anything")
+}
+
+func TestSetsLeavesCopiesWithDifferentTimes(t *testing.T) {
+	dest, first, second := createDuplicates()
+	defer os.RemoveAll(dest)
+	test_helpers.SetModified(second, time.Date(2001, 1, 2, 0, 0, 0, 0, time.Local))
+
+	result, err := Sets(dest, false)
+
+	assert.NoError(t, err)
+	assert.Equal(t, Result{Unlinked: 1}, result)
+	assert.False(t, test_helpers.SameFile(first, second), "linking would lose the newer set's modification time")
+}
+
+func TestRelinkAllCountsEachPathReplaced(t *testing.T) {
+	dest, first, second := createDuplicates()
+	defer os.RemoveAll(dest)
+	missing := filepath.Join(dest, "no-such-folder", "testfile.txt")
+
+	relinked := relinkAll(dest, first, []string{second, missing}, false)
+
+	assert.Equal(t, 1, relinked)
+	assert.True(t, test_helpers.SameFile(first, second), "paths before the failure should stay linked")
+}
+
+func TestSetsRemovesLeftoverTempLinks(t *testing.T) {
+	dest, first, _ := createDuplicates()
+	defer os.RemoveAll(dest)
+	leftover := filepath.Join(filepath.Dir(first), backup_sets.DedupeTempName)
+	if err := os.Link(first, leftover); err != nil {
+		panic(err)
+	}
+
+	_, err := Sets(dest, false)
+
+	assert.NoError(t, err)
+	_, err = os.Stat(leftover)
+	assert.True(t, os.IsNotExist(err), "links left by an interrupted dedupe should be cleaned up")
+}
+
+// createDuplicates makes two sets containing a file with the same contents
+// and modification time, plus a different file of the same size.
+func createDuplicates() (dest string, first string, second string) {
+	dest = test_helpers.CreateTmpFolder("backups")
+	first = filepath.Join(dest, "dhb-set-20010101-000000", "testfile.txt")
+	second = filepath.Join(dest, "dhb-set-20010102-000000", "renamed.txt")
+	test_helpers.WriteFile(first, "backmeup susie")
+	test_helpers.WriteFile(second, "backmeup susie")
+	test_helpers.WriteFile(filepath.Join(dest, "dhb-set-20010102-000000", "other.txt"), "backmeup sally")
+	modified := time.Date(2001, 1, 1, 0, 0, 0, 0, time.Local)
+	test_helpers.SetModified(first, modified)
+	test_helpers.SetModified(second, modified)
+	return dest, first, second
+}
+
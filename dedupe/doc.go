@@ -0,0 +1,3 @@
+// Package dedupe hardlinks identical files across existing backup sets to
+// reclaim space.
+package dedupe
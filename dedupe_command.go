@@ -0,0 +1,34 @@
+package main
+
+import (
+	"flag"
+	"fmt"
+	"github.com/timabell/disk-hog-backup/dedupe"
+	"github.com/timabell/disk-hog-backup/dhcopy"
+	"log"
+	"os"
+)
+
+// runDedupe hardlinks identical files across the existing backup sets.
+func runDedupe(args []string) {
+	flags := flag.NewFlagSet("dedupe", flag.ExitOnError)
+	dest := flags.String("destination", "", "destination folder containing backup sets")
+	dryRun := flags.Bool("dry-run", false, "only report what would be hardlinked")
+	flags.Parse(args)
+	if *dest == "" {
+		flags.Usage()
+		os.Exit(2)
+	}
+	result, err := dedupe.Sets(*dest, *dryRun)
+	if err != nil {
+		log.Fatalf("Dedupe failed: %s", err)
+	}
+	if *dryRun {
+		fmt.Printf("would hardlink %v duplicate files, saving %v\n", result.Files, dhcopy.FormatBytes(result.Bytes))
+	} else {
+		fmt.Printf("hardlinked %v duplicate files, saving %v\n", result.Files, dhcopy.FormatBytes(result.Bytes))
+	}
+	if result.Unlinked > 0 {
+		fmt.Printf("left %v files with the same contents as another copy but a different modification time or permissions\n", result.Unlinked)
+	}
+}
@@ -0,0 +1,42 @@
+package dhcopy
+
+import (
+	"fmt"
+	"github.com/timabell/disk-hog-backup/backup_sets"
+	"github.com/timabell/disk-hog-backup/dherrors"
+	"os"
+	"path/filepath"
+	"strings"
+)
+
+// isCaseInsensitive reports whether names in folder, the root of a set, are
+// case-insensitive, as on exFAT, NTFS and macOS by default, by creating a file
+// and looking for it with its name in upper case. The file is named as set
+// metadata so it isn't mistaken for backed up data if it's left behind.
+func isCaseInsensitive(folder string) (bool, error) {
+	probePath := filepath.Join(folder, backup_sets.CaseProbeName)
+	probe, err := os.Create(probePath)
+	if err != nil {
+		return false, dherrors.Wrap(dherrors.OpWrite, probePath, err)
+	}
+	probe.Close()
+	defer os.Remove(probePath)
+	_, err = os.Stat(filepath.Join(folder, strings.ToUpper(backup_sets.CaseProbeName)))
+	return err == nil, nil
+}
+
+// caseCollisions spots paths written to the destination that only differ by
+// case, which would overwrite each other on a case-insensitive destination.
+// It's shared by everything written in one backup, so paths written for
+// different entries of a source list are checked against each other too.
+type caseCollisions map[string]string
+
+// check returns an error if destPath clashes with a path already written.
+func (c caseCollisions) check(destPath string) error {
+	key := strings.ToLower(destPath)
+	if other, found := c[key]; found && other != destPath {
+		return dherrors.Wrap(dherrors.OpWrite, destPath, fmt.Errorf("%v and %v only differ by case, which the destination can't tell apart", other, destPath))
+	}
+	c[key] = destPath
+	return nil
+}
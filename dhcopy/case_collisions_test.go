@@ -0,0 +1,61 @@
+package dhcopy
+
+import (
+	"github.com/stretchr/testify/assert"
+	"github.com/timabell/disk-hog-backup/test_helpers"
+	"io/ioutil"
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestCaseCollisionsAreReported(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	makeTestFile(source, "Readme.md", "one")
+	makeTestFile(source, "README.md", "two")
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	stats := &Stats{}
+
+	// pretend the destination is case-insensitive, whatever the test machine's
+	// filesystem is
+	err := copyFolder(source, dest, 1, nil, Options{KeepGoing: true, destNames: caseCollisions{}}, stats)
+
+	assert.NoError(t, err)
+	assert.Len(t, stats.Errors, 1)
+	assert.Equal(t, 1, stats.FilesCopied)
+	contents, err := ioutil.ReadFile(filepath.Join(dest, "README.md"))
+	assert.NoError(t, err)
+	assert.Equal(t, "two", string(contents), "the first file copied shouldn't be overwritten")
+}
+
+func TestCaseCollisionsAcrossListedPaths(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	makeTestFile(source, "Readme.md", "one")
+	makeTestFile(source, "README.md", "two")
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	options := Options{destNames: caseCollisions{}}
+	stats := &Stats{}
+
+	assert.NoError(t, copyPath(source, dest, "README.md", options, stats))
+	assert.Error(t, copyPath(source, dest, "Readme.md", options, stats), "entries in the same folder should be checked against each other")
+}
+
+func TestSkippedFilesDontCollide(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	makeTestFile(source, "Readme.md", "one")
+	makeTestFile(source, "README.md", "too big")
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	stats := &Stats{}
+
+	err := copyFolder(source, dest, 1, nil, Options{MaxSize: 3, destNames: caseCollisions{}}, stats)
+
+	assert.NoError(t, err, "a file that won't be written can't overwrite anything")
+	assert.Equal(t, 1, stats.SizeSkipped)
+	assert.Equal(t, 1, stats.FilesCopied)
+}
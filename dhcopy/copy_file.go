@@ -1,29 +1,64 @@
 package dhcopy
 
 import (
+	"crypto/md5"
+	"errors"
+	"github.com/timabell/disk-hog-backup/dherrors"
 	"io"
 	"log"
 	"os"
 )
 
-func CopyFile(source string, dest string) {
+// CopyFile copies the contents of source to dest, returning the MD5 hash of
+// what was written.
+func CopyFile(source string, dest string, options Options) ([]byte, error) {
 	log.Printf("copying file %v to : %v\n", source, dest)
 
 	srcFile, err := os.Open(source)
 	if err != nil {
-		log.Fatal(err)
+		return nil, dherrors.Wrap(dherrors.OpRead, source, err)
 	}
 	defer srcFile.Close()
 
 	destFile, err := os.Create(dest)
 	if err != nil {
-		log.Fatal(err)
+		return nil, dherrors.Wrap(dherrors.OpWrite, dest, err)
 	}
-	defer destFile.Close()
 
-	bytesWritten, err := io.Copy(destFile, srcFile)
+	hash := md5.New()
+	bytesWritten, err := copyChunks(io.MultiWriter(destFile, hash), sourceReader(srcFile, options), options.ChunkSize)
 	if err != nil {
-		log.Fatal(err)
+		destFile.Close()
+		var pathErr *os.PathError
+		if errors.As(err, &pathErr) && pathErr.Path == source {
+			return nil, dherrors.Wrap(dherrors.OpRead, source, err)
+		}
+		return nil, dherrors.Wrap(dherrors.OpWrite, dest, err)
+	}
+	// some filesystems, like NFS, only report write failures on close
+	if err := destFile.Close(); err != nil {
+		return nil, dherrors.Wrap(dherrors.OpWrite, dest, err)
 	}
 	log.Printf("%v bytes copied\n", bytesWritten)
+	return hash.Sum(nil), nil
+}
+
+// copyChunks copies reader to writer reading at most chunkSize bytes at a
+// time, or io.Copy's default if chunkSize is 0. The reader is wrapped so
+// io.CopyBuffer can't hand the copy off to a WriterTo and ignore the buffer.
+func copyChunks(writer io.Writer, reader io.Reader, chunkSize int) (int64, error) {
+	var buffer []byte
+	if chunkSize > 0 {
+		buffer = make([]byte, chunkSize)
+	}
+	return io.CopyBuffer(writer, struct{ io.Reader }{reader}, buffer)
+}
+
+// sourceReader reads a file from the source within the bandwidth limit, if
+// there is one.
+func sourceReader(file *os.File, options Options) io.Reader {
+	if options.BandwidthLimit > 0 {
+		return newThrottledReader(file, options.BandwidthLimit)
+	}
+	return file
 }
@@ -1,8 +1,12 @@
 package dhcopy
 
 import (
+	"bytes"
+	"errors"
 	"github.com/stretchr/testify/assert"
+	"github.com/timabell/disk-hog-backup/dherrors"
 	"github.com/timabell/disk-hog-backup/test_helpers"
+	"io"
 	"io/ioutil"
 	"log"
 	"os"
@@ -27,9 +31,74 @@ func TestCopy(t *testing.T) {
 
 	destinationFilePath := filepath.Join(dest, theFile)
 
-	CopyFile(sourceFilePath, destinationFilePath)
+	hash, err := CopyFile(sourceFilePath, destinationFilePath, Options{})
+	assert.NoError(t, err)
+	expectedHash, err := HashFile(sourceFilePath)
+	assert.NoError(t, err)
+	assert.Equal(t, expectedHash, hash, "should return the hash of what was copied")
 
 	contentsMatches, err := test_helpers.FileContentsMatches(sourceFilePath, destinationFilePath)
 	assert.NoError(t, err)
 	assert.True(t, contentsMatches, "file contents should be copied to backup folder")
 }
+
+func TestCopyMissingSourceReportsRead(t *testing.T) {
+	dest := test_helpers.CreateTmpFolder("backups")
+	defer os.RemoveAll(dest)
+	missing := filepath.Join(dest, "not-there.txt")
+
+	_, err := CopyFile(missing, filepath.Join(dest, theFile), Options{})
+
+	var dhErr *dherrors.Error
+	if assert.True(t, errors.As(err, &dhErr), "error should say which operation failed") {
+		assert.Equal(t, dherrors.OpRead, dhErr.Op)
+		assert.Equal(t, missing, dhErr.Path)
+	}
+}
+
+func TestCopyInSmallChunks(t *testing.T) {
+	sourceFolder := test_helpers.CreateTmpFolder("orig")
+	defer os.RemoveAll(sourceFolder)
+	dest := test_helpers.CreateTmpFolder("backups")
+	defer os.RemoveAll(dest)
+	makeTestFile(sourceFolder, theFile, theText)
+	sourceFilePath := filepath.Join(sourceFolder, theFile)
+	destinationFilePath := filepath.Join(dest, theFile)
+
+	srcFile, err := os.Open(sourceFilePath)
+	assert.NoError(t, err)
+	defer srcFile.Close()
+	reader := &countingReader{reader: srcFile}
+	var copied bytes.Buffer
+	_, err = copyChunks(&copied, reader, 3)
+	assert.NoError(t, err)
+	assert.Equal(t, theText, copied.String())
+	assert.Equal(t, 3, reader.largestRead, "reads should be limited to the chunk size")
+	assert.True(t, reader.reads >= len(theText)/3, "expected the file to be read in several chunks, got %v reads", reader.reads)
+
+	hash, err := CopyFile(sourceFilePath, destinationFilePath, Options{ChunkSize: 3})
+	assert.NoError(t, err)
+	expectedHash, err := HashFile(sourceFilePath)
+	assert.NoError(t, err)
+	assert.Equal(t, expectedHash, hash)
+
+	contentsMatches, err := test_helpers.FileContentsMatches(sourceFilePath, destinationFilePath)
+	assert.NoError(t, err)
+	assert.True(t, contentsMatches, "file contents should survive being copied in small chunks")
+}
+
+// countingReader records how many reads were made and the largest buffer
+// asked for.
+type countingReader struct {
+	reader      io.Reader
+	reads       int
+	largestRead int
+}
+
+func (r *countingReader) Read(p []byte) (int, error) {
+	r.reads++
+	if len(p) > r.largestRead {
+		r.largestRead = len(p)
+	}
+	return r.reader.Read(p)
+}
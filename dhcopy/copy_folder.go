@@ -1,35 +1,264 @@
 package dhcopy
 
 import (
+	"fmt"
+	"github.com/timabell/disk-hog-backup/backup_sets"
+	"github.com/timabell/disk-hog-backup/dherrors"
 	"io/ioutil"
 	"log"
 	"os"
 	"path/filepath"
 )
 
-func CopyFolder(source string, dest string) error {
+func CopyFolder(source string, dest string, options Options, stats *Stats) error {
+	options, err := options.forSource(source)
+	if err != nil {
+		return err
+	}
+	options, err = options.forDest(dest)
+	if err != nil {
+		return err
+	}
+	return copyFolder(source, dest, 1, nil, options, stats)
+}
+
+// pendingFolder is a folder part way through being copied.
+type pendingFolder struct {
+	source string
+	dest   string
+
+	// depth is how many levels below the folder being backed up it is.
+	depth int
+
+	// ancestors holds this folder and those above it when following
+	// symlinks, to avoid going round in circles.
+	ancestors []os.FileInfo
+
+	contents []os.FileInfo
+	next     int
+}
+
+// openFolder lists the contents of a folder ready to copy them.
+func openFolder(source string, dest string, depth int, ancestors []os.FileInfo, options Options) (*pendingFolder, error) {
 	log.Printf("backing up folder %v into %v\n", source, dest)
 	contents, err := ioutil.ReadDir(source)
 	if err != nil {
-		log.Fatal(err)
+		return nil, dherrors.Wrap(dherrors.OpRead, source, err)
 	}
+	if options.FollowSymlinks {
+		info, err := os.Stat(source)
+		if err != nil {
+			return nil, dherrors.Wrap(dherrors.OpRead, source, err)
+		}
+		// copy rather than share ancestors with sibling folders
+		ancestors = append(ancestors[:len(ancestors):len(ancestors)], info)
+	}
+	return &pendingFolder{source: source, dest: dest, depth: depth, ancestors: ancestors, contents: contents}, nil
+}
+
+// copyFolder copies the contents of source, which are depth levels below the
+// folder being backed up. ancestors holds the folders above it when following
+// symlinks. Folders are walked with a stack rather than recursion, so the
+// depth of the tree is only limited by memory.
+func copyFolder(source string, dest string, depth int, ancestors []os.FileInfo, options Options, stats *Stats) error {
+	root, err := openFolder(source, dest, depth, ancestors, options)
+	if err != nil {
+		return err
+	}
+	return copyContents(root, options, stats)
+}
 
-	for _, item := range contents {
-		if item.IsDir() {
-			destFolder := filepath.Join(dest, item.Name())
-			err := os.Mkdir(destFolder, os.ModePerm)
-			if err != nil {
-				log.Fatal(err)
+// copyContents copies everything in an opened folder and the folders within
+// it.
+func copyContents(root *pendingFolder, options Options, stats *Stats) error {
+	stack := []*pendingFolder{root}
+	for len(stack) > 0 {
+		current := stack[len(stack)-1]
+		if current.next >= len(current.contents) {
+			stack = stack[:len(stack)-1]
+			continue
+		}
+		item := current.contents[current.next]
+		current.next++
+		subfolder, err := copyItem(current, item, options, stats)
+		if err := keepGoing(err, options, stats); err != nil {
+			return err
+		}
+		if subfolder != nil {
+			stack = append(stack, subfolder)
+		}
+	}
+	return nil
+}
+
+// copyItem copies one item from a folder, returning the opened subfolder if
+// it's a folder whose contents should be copied next.
+func copyItem(parent *pendingFolder, item os.FileInfo, options Options, stats *Stats) (*pendingFolder, error) {
+	itemPath := filepath.Join(parent.source, item.Name())
+	destPath := filepath.Join(parent.dest, item.Name())
+	if !options.IncludeSetMetadata && !item.IsDir() && backup_sets.IsSetFolder(filepath.Base(parent.source)) && backup_sets.IsMetadataFile(item.Name()) {
+		log.Printf("skipping backup set metadata %v\n", itemPath)
+		return nil, nil
+	}
+	if options.filteredOut(itemPath, item.IsDir()) {
+		log.Printf("skipping %v, excluded by filter rules\n", itemPath)
+		stats.FilterSkipped++
+		return nil, nil
+	}
+	if item.Mode()&os.ModeSymlink != 0 {
+		if !options.FollowSymlinks {
+			if err := checkMetadataClash(parent, item.Name(), itemPath); err != nil {
+				return nil, err
 			}
-			dirPath := filepath.Join(source, item.Name())
-			if err := CopyFolder(dirPath, destFolder); err != nil {
-				return err
+			if err := options.checkCaseCollision(destPath); err != nil {
+				return nil, err
 			}
-			continue
+			return nil, copySymlink(itemPath, destPath, options, stats)
+		}
+		target, err := os.Stat(itemPath)
+		if os.IsNotExist(err) {
+			log.Printf("skipping %v, it's a symlink to something that doesn't exist\n", itemPath)
+			stats.DanglingSymlinks++
+			return nil, nil
+		}
+		if err != nil {
+			return nil, dherrors.Wrap(dherrors.OpRead, itemPath, err)
+		}
+		item = target
+	}
+	if !options.NoDefaultSkips && isDefaultSkip(itemPath, item) {
+		log.Printf("skipping %v\n", itemPath)
+		stats.DefaultSkipped++
+		return nil, nil
+	}
+	if err := checkMetadataClash(parent, item.Name(), itemPath); err != nil {
+		return nil, err
+	}
+	if item.IsDir() {
+		if isAncestor(item, parent.ancestors) {
+			log.Printf("skipping %v, it links back to a folder that's already being copied\n", itemPath)
+			return nil, nil
 		}
-		itemPath := filepath.Join(source, item.Name())
-		destFile := filepath.Join(dest, item.Name())
-		CopyFile(itemPath, destFile)
+		if err := options.checkCaseCollision(destPath); err != nil {
+			return nil, err
+		}
+		if err := os.Mkdir(destPath, os.ModePerm); err != nil {
+			return nil, dherrors.Wrap(dherrors.OpWrite, destPath, err)
+		}
+		if options.MaxDepth > 0 && parent.depth >= options.MaxDepth {
+			return nil, nil
+		}
+		if options.onOtherFileSystem(item) {
+			log.Printf("not descending into %v, it's on a different filesystem\n", itemPath)
+			return nil, nil
+		}
+		if options.ExactPaths {
+			return nil, nil
+		}
+		return openFolder(itemPath, destPath, parent.depth+1, parent.ancestors, options)
+	}
+	if isSpecialFile(item) {
+		skipSpecialFile(itemPath, item, options, stats)
+		return nil, nil
+	}
+	if options.StructureOnly {
+		return nil, nil
+	}
+	return nil, copyFile(itemPath, destPath, item, options, stats)
+}
+
+// checkMetadataClash returns an error if an item at the root of the source
+// has the same name as one of the set's own files, as the root of the source
+// is copied into the root of the set. It's checked once filters and skips
+// have had their say, so the item can be left out with a filter rule.
+func checkMetadataClash(parent *pendingFolder, name string, itemPath string) error {
+	if parent.depth == 1 && backup_sets.IsMetadataFile(name) {
+		return fmt.Errorf("%v can't be backed up as it has the same name as one of disk-hog-backup's own files in the set, rename it or leave it out with -filter '- /%v'", itemPath, name)
+	}
+	return nil
+}
+
+// copyFile copies a file, or hardlinks it to an identical copy when
+// deduplicating, and counts it in stats. Files outside the size limits or
+// older than the changed-since cutoff are skipped, as are volatile files if
+// options say so.
+func copyFile(source string, dest string, info os.FileInfo, options Options, stats *Stats) error {
+	if options.outsideSizeLimits(info.Size()) {
+		log.Printf("skipping %v, %v is outside the size limits\n", source, FormatBytes(info.Size()))
+		stats.SizeSkipped++
+		return nil
+	}
+	if !options.ChangedSince.IsZero() && !info.ModTime().After(options.ChangedSince) {
+		stats.UnchangedSkipped++
+		return nil
+	}
+	volatile := options.Volatile != VolatileCopy && isVolatile(info.Name())
+	if volatile && options.Volatile == VolatileSkip {
+		log.Printf("skipping %v, it's probably in use\n", source)
+		stats.VolatileSkipped++
+		return nil
+	}
+	if err := options.checkCaseCollision(dest); err != nil {
+		return err
+	}
+	if options.Dedupe {
+		linked, err := linkDuplicate(source, dest, info, options, stats)
+		if err != nil {
+			return err
+		}
+		if linked {
+			if options.FolderBreakdown {
+				stats.addFolderTotal(options.topLevelFolder(source), info.Size())
+			}
+			return nil
+		}
+	}
+	var hash []byte
+	err := retry(func() (err error) {
+		hash, err = CopyFile(source, dest, options)
+		return err
+	}, options, stats)
+	if err != nil {
+		return err
+	}
+	// keep the modification time so the set can be compared with the source
+	// without reading every file. Some destinations, like SMB shares and
+	// FAT, won't set times, which only makes comparing slower so the copy
+	// still counts.
+	if err := os.Chtimes(dest, info.ModTime(), info.ModTime()); err != nil {
+		log.Printf("warning: couldn't set the modification time of %v: %s\n", dest, err)
+		stats.TimesNotKept++
+	}
+	if options.VerifyWrites {
+		if err := verifyCopy(dest, hash, stats); err != nil {
+			return err
+		}
+	}
+	stats.addCopied(info.Name(), info.Size())
+	if options.FolderBreakdown {
+		stats.addFolderTotal(options.topLevelFolder(source), info.Size())
+	}
+	if options.LargestFiles > 0 {
+		stats.addLargest(CopiedFile{options.relativePath(source), info.Size()}, options.LargestFiles)
+	}
+	if volatile {
+		log.Printf("warning: %v is probably in use, the copy may be inconsistent\n", source)
+		stats.VolatileCopied++
+	}
+	if options.Dedupe {
+		stats.rememberCopy(dest, info, hash)
+	}
+	return nil
+}
+
+// keepGoing records err in stats and swallows it if options say to carry on
+// after failures, otherwise it hands it back to stop the backup. A destination
+// that has gone read-only always stops the backup as nothing else will work.
+func keepGoing(err error, options Options, stats *Stats) error {
+	if err == nil || !options.KeepGoing || dherrors.IsReadOnly(err) {
+		return err
 	}
+	log.Printf("carrying on after error: %s\n", err)
+	stats.Errors = append(stats.Errors, err)
 	return nil
 }
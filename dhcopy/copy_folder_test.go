@@ -2,11 +2,13 @@ package dhcopy
 
 import (
 	"github.com/stretchr/testify/assert"
+	"github.com/timabell/disk-hog-backup/backup_sets"
 	"github.com/timabell/disk-hog-backup/test_helpers"
 	"io/ioutil"
 	"os"
 	"path/filepath"
 	"testing"
+	"time"
 )
 
 const emptyFolder = "NothingInHere"
@@ -19,7 +21,7 @@ func TestCopiesFile(t *testing.T) {
 	dest := test_helpers.CreateTmpFolder(backupFolderName)
 	defer os.RemoveAll(dest)
 
-	CopyFolder(source, dest)
+	CopyFolder(source, dest, Options{}, &Stats{})
 
 	// Just a quick check that recursion is including files.
 	// Full testing of files is is in the file copier tests.
@@ -27,6 +29,24 @@ func TestCopiesFile(t *testing.T) {
 	assert.NoError(t, err)
 }
 
+func TestKeepsModificationTime(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	makeTestFile(source, "testfile.txt", "backmeup susie")
+	modified := time.Date(2001, 1, 1, 0, 0, 0, 0, time.UTC)
+	if err := os.Chtimes(filepath.Join(source, "testfile.txt"), modified, modified); err != nil {
+		panic(err)
+	}
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+
+	assert.NoError(t, CopyFolder(source, dest, Options{}, &Stats{}))
+
+	info, err := os.Stat(filepath.Join(dest, "testfile.txt"))
+	assert.NoError(t, err)
+	assert.True(t, info.ModTime().Equal(modified), "expected modification time %v, got %v", modified, info.ModTime())
+}
+
 func TestCopyEmptyFolder(t *testing.T) {
 	source := createSource()
 	defer os.RemoveAll(source)
@@ -39,11 +59,212 @@ func TestCopyEmptyFolder(t *testing.T) {
 	dest := test_helpers.CreateTmpFolder(backupFolderName)
 	defer os.RemoveAll(dest)
 
-	CopyFolder(source, dest)
+	CopyFolder(source, dest, Options{}, &Stats{})
 
 	checkEmptyFolderCopied(t, dest)
 }
 
+func TestStructureOnly(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	if err := os.MkdirAll(filepath.Join(source, emptyFolder), os.ModePerm); err != nil {
+		panic(err)
+	}
+	makeTestFile(source, "testfile.txt", "backmeup susie")
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+
+	err := CopyFolder(source, dest, Options{StructureOnly: true}, &Stats{})
+	assert.NoError(t, err)
+
+	checkEmptyFolderCopied(t, dest)
+	_, err = os.Stat(filepath.Join(dest, "testfile.txt"))
+	assert.True(t, os.IsNotExist(err), "files should not be copied")
+}
+
+func TestMaxDepth(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	if err := os.MkdirAll(filepath.Join(source, "one", "two", "three"), os.ModePerm); err != nil {
+		panic(err)
+	}
+	makeTestFile(filepath.Join(source, "one"), "shallow.txt", "keep me")
+	makeTestFile(filepath.Join(source, "one", "two"), "deep.txt", "too deep")
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+
+	err := CopyFolder(source, dest, Options{MaxDepth: 2}, &Stats{})
+	assert.NoError(t, err)
+
+	_, err = os.Stat(filepath.Join(dest, "one", "shallow.txt"))
+	assert.NoError(t, err, "files within the depth limit should be copied")
+	_, err = os.Stat(filepath.Join(dest, "one", "two"))
+	assert.NoError(t, err, "folders at the depth limit should be created")
+	_, err = os.Stat(filepath.Join(dest, "one", "two", "deep.txt"))
+	assert.True(t, os.IsNotExist(err), "contents below the depth limit should not be copied")
+}
+
+func TestSkipsMetadataOfSetsInSource(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	oldSet := filepath.Join(source, backup_sets.GenerateName(test_helpers.TimeFixer()))
+	if err := os.Mkdir(oldSet, os.ModePerm); err != nil {
+		panic(err)
+	}
+	makeTestFile(oldSet, backup_sets.ErrorReportName, "read failed on x")
+	makeTestFile(oldSet, "testfile.txt", "backmeup susie")
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+
+	err := CopyFolder(source, dest, Options{}, &Stats{})
+	assert.NoError(t, err)
+
+	destSet := filepath.Join(dest, filepath.Base(oldSet))
+	_, err = os.Stat(filepath.Join(destSet, backup_sets.ErrorReportName))
+	assert.True(t, os.IsNotExist(err), "set metadata should not be treated as data")
+	_, err = os.Stat(filepath.Join(destSet, "testfile.txt"))
+	assert.NoError(t, err, "data in old sets should still be copied")
+}
+
+func TestRefusesFilesClashingWithSetMetadata(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	makeTestFile(source, backup_sets.ErrorReportName, "my own notes")
+	if err := os.Mkdir(filepath.Join(source, "sub"), os.ModePerm); err != nil {
+		panic(err)
+	}
+	makeTestFile(filepath.Join(source, "sub"), backup_sets.ErrorReportName, "fine down here")
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	stats := &Stats{}
+
+	err := CopyFolder(source, dest, Options{KeepGoing: true}, stats)
+	assert.NoError(t, err)
+
+	assert.Len(t, stats.Errors, 1, "a file at the root named like set metadata should be reported")
+	_, err = os.Stat(filepath.Join(dest, backup_sets.ErrorReportName))
+	assert.True(t, os.IsNotExist(err), "it should not be copied where the set's own file goes")
+	_, err = os.Stat(filepath.Join(dest, "sub", backup_sets.ErrorReportName))
+	assert.NoError(t, err, "files with the same name further down don't clash")
+}
+
+func TestFilterLeavesOutFilesClashingWithSetMetadata(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	makeTestFile(source, backup_sets.ErrorReportName, "my own notes")
+	makeTestFile(source, "testfile.txt", "backmeup susie")
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	stats := &Stats{}
+
+	rule, err := ParseFilterRule("- /" + backup_sets.ErrorReportName)
+	assert.NoError(t, err)
+	err = CopyFolder(source, dest, Options{Filters: []FilterRule{rule}}, stats)
+
+	assert.NoError(t, err, "the filter suggested in the error should let the backup go through")
+	assert.Equal(t, 1, stats.FilterSkipped)
+	assert.Equal(t, 1, stats.FilesCopied, "the rest of the source should still be copied")
+}
+
+func TestSizeLimits(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	makeTestFile(source, "small.txt", "x")
+	makeTestFile(source, "medium.txt", "xxxxx")
+	makeTestFile(source, "large.txt", "xxxxxxxxxx")
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	stats := &Stats{}
+
+	err := CopyFolder(source, dest, Options{MinSize: 2, MaxSize: 9}, stats)
+	assert.NoError(t, err)
+
+	_, err = os.Stat(filepath.Join(dest, "medium.txt"))
+	assert.NoError(t, err, "files within the limits should be copied")
+	_, err = os.Stat(filepath.Join(dest, "small.txt"))
+	assert.True(t, os.IsNotExist(err), "files under the minimum size should be skipped")
+	_, err = os.Stat(filepath.Join(dest, "large.txt"))
+	assert.True(t, os.IsNotExist(err), "files over the maximum size should be skipped")
+	assert.Equal(t, 2, stats.SizeSkipped)
+}
+
+func TestChangedSince(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	makeTestFile(source, "old.txt", "stale")
+	makeTestFile(source, "new.txt", "fresh")
+	cutoff := time.Now().Add(-time.Hour)
+	longAgo := cutoff.Add(-24 * time.Hour)
+	if err := os.Chtimes(filepath.Join(source, "old.txt"), longAgo, longAgo); err != nil {
+		panic(err)
+	}
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	stats := &Stats{}
+
+	err := CopyFolder(source, dest, Options{ChangedSince: cutoff}, stats)
+	assert.NoError(t, err)
+
+	_, err = os.Stat(filepath.Join(dest, "new.txt"))
+	assert.NoError(t, err, "recently modified files should be copied")
+	_, err = os.Stat(filepath.Join(dest, "old.txt"))
+	assert.True(t, os.IsNotExist(err), "files not modified since the cutoff should be skipped")
+	assert.Equal(t, 1, stats.UnchangedSkipped)
+}
+
+func TestFolderBreakdown(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	for _, folder := range []string{"music/albums", "documents"} {
+		if err := os.MkdirAll(filepath.Join(source, folder), os.ModePerm); err != nil {
+			panic(err)
+		}
+	}
+	makeTestFile(filepath.Join(source, "music/albums"), "song.mp3", "la la la la")
+	makeTestFile(filepath.Join(source, "music"), "playlist.m3u", "song.mp3")
+	makeTestFile(filepath.Join(source, "documents"), "letter.txt", "hi")
+	makeTestFile(source, "notes.txt", "x")
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	stats := &Stats{}
+
+	err := CopyFolder(source, dest, Options{FolderBreakdown: true}, stats)
+	assert.NoError(t, err)
+
+	assert.Equal(t, map[string]*Total{
+		"music":     {Files: 2, Bytes: int64(len("la la la la") + len("song.mp3"))},
+		"documents": {Files: 1, Bytes: int64(len("hi"))},
+		".":         {Files: 1, Bytes: int64(len("x"))},
+	}, stats.Folders)
+}
+
+func TestFolderBreakdownIncludesDedupedFiles(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	for _, folder := range []string{"music", "documents"} {
+		if err := os.Mkdir(filepath.Join(source, folder), os.ModePerm); err != nil {
+			panic(err)
+		}
+	}
+	makeTestFile(filepath.Join(source, "documents"), "song.mp3", "la la la la")
+	makeTestFile(filepath.Join(source, "music"), "song.mp3", "la la la la")
+	modified := time.Date(2001, 1, 1, 0, 0, 0, 0, time.Local)
+	test_helpers.SetModified(filepath.Join(source, "documents", "song.mp3"), modified)
+	test_helpers.SetModified(filepath.Join(source, "music", "song.mp3"), modified)
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	stats := &Stats{}
+
+	err := CopyFolder(source, dest, Options{FolderBreakdown: true, Dedupe: true}, stats)
+	assert.NoError(t, err)
+
+	assert.Equal(t, 1, stats.DedupedFiles)
+	assert.Equal(t, map[string]*Total{
+		"music":     {Files: 1, Bytes: int64(len("la la la la"))},
+		"documents": {Files: 1, Bytes: int64(len("la la la la"))},
+	}, stats.Folders, "hardlinked duplicates should count towards their folder")
+}
+
 func checkEmptyFolderCopied(t *testing.T, dest string) {
 	dirPath := filepath.Join(dest, emptyFolder)
 	dir, err := ioutil.ReadDir(dirPath)
@@ -0,0 +1,129 @@
+package dhcopy
+
+import (
+	"fmt"
+	"github.com/timabell/disk-hog-backup/dherrors"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+)
+
+// CopyPaths copies only the listed paths from source into dest, recreating
+// the folders leading to each one. Paths are relative to source, or absolute
+// paths that are inside it. Listed folders are copied with everything in
+// them, unless options say to copy exact paths only.
+func CopyPaths(source string, dest string, paths []string, options Options, stats *Stats) error {
+	options, err := options.forSource(source)
+	if err != nil {
+		return err
+	}
+	options, err = options.forDest(dest)
+	if err != nil {
+		return err
+	}
+	var relPaths []string
+	for _, path := range paths {
+		relPath, err := relativeToSource(source, path)
+		if err := keepGoing(err, options, stats); err != nil {
+			return err
+		}
+		if relPath != "" {
+			relPaths = append(relPaths, relPath)
+		}
+	}
+	for _, relPath := range listedPaths(relPaths, options.ExactPaths) {
+		if err := keepGoing(copyPath(source, dest, relPath, options, stats), options, stats); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// listedPaths sorts paths relative to the source so that folders come before
+// what's in them, which are then only created once, and drops duplicates.
+// Unless exact, paths within listed folders are dropped too as they're copied
+// along with the folder, so lists like the output of find work. When exact,
+// the source folder itself is dropped as there's nothing to copy for it.
+func listedPaths(relPaths []string, exact bool) []string {
+	sorted := append([]string{}, relPaths...)
+	sort.Slice(sorted, func(i, j int) bool {
+		return folderOrder(sorted[i]) < folderOrder(sorted[j])
+	})
+	var listed []string
+	for _, relPath := range sorted {
+		if relPath == "." {
+			if exact {
+				continue
+			}
+			return []string{"."}
+		}
+		if len(listed) > 0 {
+			last := listed[len(listed)-1]
+			if relPath == last || (!exact && strings.HasPrefix(relPath, last+string(filepath.Separator))) {
+				continue
+			}
+		}
+		listed = append(listed, relPath)
+	}
+	return listed
+}
+
+// folderOrder returns a key that sorts everything in a folder straight after
+// the folder, before anything that merely shares a prefix with it.
+func folderOrder(relPath string) string {
+	return strings.Replace(relPath, string(filepath.Separator), "\x00", -1)
+}
+
+// copyPath copies a path relative to source the same way as it would be
+// copied as part of its folder, so symlinks, filters and skips are handled
+// the same. Folders' contents are copied too unless options say to copy
+// exact paths only, in which case copyItem doesn't open them.
+func copyPath(source string, dest string, relPath string, options Options, stats *Stats) error {
+	if relPath == "." {
+		return copyFolder(source, dest, 1, nil, options, stats)
+	}
+	sourcePath := filepath.Join(source, relPath)
+	info, err := os.Lstat(sourcePath)
+	if err != nil {
+		return dherrors.Wrap(dherrors.OpRead, sourcePath, err)
+	}
+	parentPath := filepath.Dir(relPath)
+	destParent := filepath.Join(dest, parentPath)
+	if err := os.MkdirAll(destParent, os.ModePerm); err != nil {
+		return dherrors.Wrap(dherrors.OpWrite, destParent, err)
+	}
+	parent := &pendingFolder{source: filepath.Join(source, parentPath), dest: destParent, depth: folderDepth(parentPath)}
+	subfolder, err := copyItem(parent, info, options, stats)
+	if err != nil || subfolder == nil {
+		return err
+	}
+	return copyContents(subfolder, options, stats)
+}
+
+// folderDepth returns the depth of the contents of a folder within the
+// source, as counted by MaxDepth.
+func folderDepth(relPath string) int {
+	if relPath == "." {
+		return 1
+	}
+	return len(strings.Split(relPath, string(filepath.Separator))) + 1
+}
+
+func relativeToSource(source string, path string) (string, error) {
+	relPath := filepath.Clean(path)
+	if filepath.IsAbs(path) {
+		absSource, err := filepath.Abs(source)
+		if err != nil {
+			return "", err
+		}
+		relPath, err = filepath.Rel(absSource, path)
+		if err != nil {
+			return "", err
+		}
+	}
+	if relPath == ".." || strings.HasPrefix(relPath, ".."+string(filepath.Separator)) {
+		return "", fmt.Errorf("%v is not inside source folder %v", path, source)
+	}
+	return relPath, nil
+}
@@ -0,0 +1,114 @@
+package dhcopy
+
+import (
+	"github.com/stretchr/testify/assert"
+	"github.com/timabell/disk-hog-backup/test_helpers"
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestCopiesOnlyListedPaths(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	if err := os.MkdirAll(filepath.Join(source, "wanted", "nested"), os.ModePerm); err != nil {
+		panic(err)
+	}
+	makeTestFile(filepath.Join(source, "wanted", "nested"), "deep.txt", "keep me")
+	makeTestFile(source, "single.txt", "keep me too")
+	makeTestFile(source, "unwanted.txt", "leave me behind")
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+
+	err := CopyPaths(source, dest, []string{"wanted", filepath.Join(source, "single.txt")}, Options{}, &Stats{})
+	assert.NoError(t, err)
+
+	_, err = os.Stat(filepath.Join(dest, "wanted", "nested", "deep.txt"))
+	assert.NoError(t, err, "listed folder should be copied recursively")
+	_, err = os.Stat(filepath.Join(dest, "single.txt"))
+	assert.NoError(t, err, "absolute path inside source should be copied")
+	_, err = os.Stat(filepath.Join(dest, "unwanted.txt"))
+	assert.True(t, os.IsNotExist(err), "unlisted file should not be copied")
+}
+
+func TestCopiesFindStyleList(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	if err := os.MkdirAll(filepath.Join(source, "wanted", "nested"), os.ModePerm); err != nil {
+		panic(err)
+	}
+	makeTestFile(filepath.Join(source, "wanted", "nested"), "deep.txt", "keep me")
+	makeTestFile(source, "single.txt", "keep me too")
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	// as from find . -print0, every folder is listed as well as what's in it
+	list := []string{".", "./wanted", "./wanted/nested", "./wanted/nested/deep.txt", "./single.txt"}
+
+	err := CopyPaths(source, dest, list, Options{}, &Stats{})
+	assert.NoError(t, err, "nested entries shouldn't be copied twice")
+
+	_, err = os.Stat(filepath.Join(dest, "wanted", "nested", "deep.txt"))
+	assert.NoError(t, err)
+	_, err = os.Stat(filepath.Join(dest, "single.txt"))
+	assert.NoError(t, err)
+}
+
+func TestExactPathsOnlyCopiesListedPaths(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	if err := os.MkdirAll(filepath.Join(source, "wanted", "nested"), os.ModePerm); err != nil {
+		panic(err)
+	}
+	makeTestFile(filepath.Join(source, "wanted", "nested"), "deep.txt", "keep me")
+	makeTestFile(filepath.Join(source, "wanted"), "unlisted.txt", "leave me behind")
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	// a file listed before the folders it's in, as an indexer might
+	list := []string{"./wanted/nested/deep.txt", ".", "./wanted/nested", "./wanted"}
+
+	err := CopyPaths(source, dest, list, Options{ExactPaths: true}, &Stats{})
+	assert.NoError(t, err)
+
+	_, err = os.Stat(filepath.Join(dest, "wanted", "nested", "deep.txt"))
+	assert.NoError(t, err, "listed file should be copied")
+	_, err = os.Stat(filepath.Join(dest, "wanted", "unlisted.txt"))
+	assert.True(t, os.IsNotExist(err), "contents of listed folders shouldn't be copied unless listed")
+}
+
+func TestListedPaths(t *testing.T) {
+	sep := string(filepath.Separator)
+	relPaths := []string{"a" + sep + "b", "a-b", "a", "c", "a" + sep + "b", "."}
+
+	assert.Equal(t, []string{"."}, listedPaths(relPaths, false))
+	assert.Equal(t, []string{"a", "a-b", "c"}, listedPaths(relPaths[:5], false))
+	assert.Equal(t, []string{"a", "a" + sep + "b", "a-b", "c"}, listedPaths(relPaths, true))
+}
+
+func TestRejectsPathsOutsideSource(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+
+	err := CopyPaths(source, dest, []string{"../escape.txt"}, Options{}, &Stats{})
+	assert.Error(t, err)
+}
+
+func TestListedSymlinkIsRecreated(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	if err := os.MkdirAll(filepath.Join(source, "target"), os.ModePerm); err != nil {
+		panic(err)
+	}
+	makeTestFile(filepath.Join(source, "target"), "inside.txt", "not through the link")
+	test_helpers.MakeSymlink("target", filepath.Join(source, "link"))
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+
+	err := CopyPaths(source, dest, []string{"link"}, Options{}, &Stats{})
+	assert.NoError(t, err)
+
+	target, err := os.Readlink(filepath.Join(dest, "link"))
+	assert.NoError(t, err, "listed symlink should be recreated as a link")
+	assert.Equal(t, "target", target)
+}
@@ -0,0 +1,71 @@
+package dhcopy
+
+import (
+	"crypto/md5"
+	"github.com/timabell/disk-hog-backup/dherrors"
+	"io"
+	"log"
+	"os"
+)
+
+// contentKey identifies file contents when looking for duplicates. The
+// modification time is part of it as hardlinked files share one, so linking
+// files with different times would lose one of them.
+type contentKey struct {
+	size    int64
+	modTime int64
+	hash    string
+}
+
+// linkDuplicate hardlinks dest to a file with the same contents and
+// modification time already copied in this backup, reporting whether it found
+// one. Source is only read if something of the same size has been copied, and
+// a file with the same hash is compared byte for byte before linking to it.
+func linkDuplicate(source string, dest string, info os.FileInfo, options Options, stats *Stats) (bool, error) {
+	size := info.Size()
+	if !stats.copiedSizes[size] {
+		return false, nil
+	}
+	file, err := os.Open(source)
+	if err != nil {
+		return false, dherrors.Wrap(dherrors.OpRead, source, err)
+	}
+	defer file.Close()
+	hash := md5.New()
+	if _, err := io.Copy(hash, sourceReader(file, options)); err != nil {
+		return false, dherrors.Wrap(dherrors.OpRead, source, err)
+	}
+	existing, found := stats.copiedContent[contentKey{size, info.ModTime().UnixNano(), string(hash.Sum(nil))}]
+	if !found {
+		return false, nil
+	}
+	if _, err := file.Seek(0, io.SeekStart); err != nil {
+		return false, dherrors.Wrap(dherrors.OpRead, source, err)
+	}
+	same, err := sameContentsAs(sourceReader(file, options), source, existing)
+	if err != nil {
+		return false, err
+	}
+	if !same {
+		log.Printf("%v has the same hash as %v but different contents, copying it\n", source, existing)
+		return false, nil
+	}
+	if err := os.Link(existing, dest); err != nil {
+		log.Printf("couldn't hardlink %v to %v, copying instead: %s\n", dest, existing, err)
+		return false, nil
+	}
+	log.Printf("hardlinked duplicate %v to %v\n", dest, existing)
+	stats.DedupedFiles++
+	stats.DedupedBytes += size
+	return true, nil
+}
+
+// rememberCopy records where contents were copied to for linkDuplicate.
+func (s *Stats) rememberCopy(dest string, info os.FileInfo, hash []byte) {
+	if s.copiedContent == nil {
+		s.copiedSizes = map[int64]bool{}
+		s.copiedContent = map[contentKey]string{}
+	}
+	s.copiedSizes[info.Size()] = true
+	s.copiedContent[contentKey{info.Size(), info.ModTime().UnixNano(), string(hash)}] = dest
+}
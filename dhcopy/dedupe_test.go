@@ -0,0 +1,85 @@
+package dhcopy
+
+import (
+	"github.com/stretchr/testify/assert"
+	"github.com/timabell/disk-hog-backup/test_helpers"
+	"os"
+	"path/filepath"
+	"testing"
+	"time"
+)
+
+func TestDedupeHardlinksIdenticalFiles(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	makeTestFile(source, "a.txt", "same stuff")
+	makeTestFile(source, "b.txt", "same stuff")
+	makeTestFile(source, "c.txt", "diff stuff")
+	modified := time.Date(2001, 1, 1, 0, 0, 0, 0, time.Local)
+	for _, name := range []string{"a.txt", "b.txt", "c.txt"} {
+		test_helpers.SetModified(filepath.Join(source, name), modified)
+	}
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	stats := &Stats{}
+
+	err := CopyFolder(source, dest, Options{Dedupe: true}, stats)
+	assert.NoError(t, err)
+
+	assert.True(t, test_helpers.SameFile(filepath.Join(dest, "a.txt"), filepath.Join(dest, "b.txt")), "identical files should be hardlinked")
+	assert.False(t, test_helpers.SameFile(filepath.Join(dest, "a.txt"), filepath.Join(dest, "c.txt")), "same size but different contents should be copied")
+	assert.Equal(t, 1, stats.DedupedFiles)
+	assert.Equal(t, int64(len("same stuff")), stats.DedupedBytes)
+}
+
+func TestDedupeComparesContentsBeforeLinking(t *testing.T) {
+	folder := test_helpers.CreateTmpFolder("dedupe")
+	defer os.RemoveAll(folder)
+	makeTestFile(folder, "copied.txt", "diff stuff")
+	makeTestFile(folder, "source.txt", "same stuff")
+	hash, err := HashFile(filepath.Join(folder, "source.txt"))
+	assert.NoError(t, err)
+	info, err := os.Stat(filepath.Join(folder, "source.txt"))
+	assert.NoError(t, err)
+	stats := &Stats{}
+	// as if the hashes of the two files had collided
+	stats.rememberCopy(filepath.Join(folder, "copied.txt"), info, hash)
+
+	linked, err := linkDuplicate(filepath.Join(folder, "source.txt"), filepath.Join(folder, "dest.txt"), info, Options{}, stats)
+
+	assert.NoError(t, err)
+	assert.False(t, linked, "files with the same hash but different contents should not be linked")
+	assert.Equal(t, 0, stats.DedupedFiles)
+}
+
+func TestDedupeCopiesFilesWithDifferentTimes(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	makeTestFile(source, "a.txt", "same stuff")
+	makeTestFile(source, "b.txt", "same stuff")
+	test_helpers.SetModified(filepath.Join(source, "a.txt"), time.Date(2001, 1, 1, 0, 0, 0, 0, time.Local))
+	test_helpers.SetModified(filepath.Join(source, "b.txt"), time.Date(2001, 1, 2, 0, 0, 0, 0, time.Local))
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	stats := &Stats{}
+
+	err := CopyFolder(source, dest, Options{Dedupe: true}, stats)
+	assert.NoError(t, err)
+
+	assert.False(t, test_helpers.SameFile(filepath.Join(dest, "a.txt"), filepath.Join(dest, "b.txt")), "linking would give one of them the other's modification time")
+	assert.Equal(t, 0, stats.DedupedFiles)
+}
+
+func TestNoDedupeByDefault(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	makeTestFile(source, "a.txt", "same stuff")
+	makeTestFile(source, "b.txt", "same stuff")
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+
+	err := CopyFolder(source, dest, Options{}, &Stats{})
+	assert.NoError(t, err)
+
+	assert.False(t, test_helpers.SameFile(filepath.Join(dest, "a.txt"), filepath.Join(dest, "b.txt")))
+}
@@ -0,0 +1,77 @@
+package dhcopy
+
+import (
+	"debug/elf"
+	"encoding/binary"
+	"io"
+	"os"
+	"path/filepath"
+)
+
+// Files that are never wanted in a backup: swap and hibernation files.
+// Matched against each file name with filepath.Match.
+var defaultSkippedFiles = []string{
+	"swapfile",
+	"swap.img",
+	"pagefile.sys",
+	"hiberfil.sys",
+	"swapfile.sys",
+}
+
+// Names core dumps are written with. Plenty of other files are called this
+// too, so they're only skipped if they really are core dumps.
+var coreDumpNames = []string{
+	"core",
+	"core.[0-9]*",
+}
+
+// Folders that are never wanted in a backup: trash bins and fsck's lost+found.
+var defaultSkippedFolders = []string{
+	".Trash",
+	".Trash-*",
+	"$RECYCLE.BIN",
+	"$Recycle.Bin",
+	"lost+found",
+}
+
+func isDefaultSkip(path string, item os.FileInfo) bool {
+	if item.IsDir() {
+		return matchesAny(defaultSkippedFolders, item.Name())
+	}
+	if !item.Mode().IsRegular() {
+		return false
+	}
+	return matchesAny(defaultSkippedFiles, item.Name()) || (matchesAny(coreDumpNames, item.Name()) && isCoreDump(path))
+}
+
+func matchesAny(patterns []string, name string) bool {
+	for _, pattern := range patterns {
+		if matched, _ := filepath.Match(pattern, name); matched {
+			return true
+		}
+	}
+	return false
+}
+
+// isCoreDump reports whether a file starts with an ELF header for a core
+// file. Files that can't be read aren't treated as core dumps, so copying
+// them reports the error.
+func isCoreDump(path string) bool {
+	file, err := os.Open(path)
+	if err != nil {
+		return false
+	}
+	defer file.Close()
+	header := make([]byte, 18) // up to and including e_type
+	if _, err := io.ReadFull(file, header); err != nil {
+		return false
+	}
+	if string(header[:4]) != elf.ELFMAG {
+		return false
+	}
+	var byteOrder binary.ByteOrder = binary.LittleEndian
+	if elf.Data(header[elf.EI_DATA]) == elf.ELFDATA2MSB {
+		byteOrder = binary.BigEndian
+	}
+	return elf.Type(byteOrder.Uint16(header[16:])) == elf.ET_CORE
+}
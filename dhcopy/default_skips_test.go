@@ -0,0 +1,59 @@
+package dhcopy
+
+import (
+	"github.com/stretchr/testify/assert"
+	"github.com/timabell/disk-hog-backup/test_helpers"
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestSkipsUnwantedItemsByDefault(t *testing.T) {
+	source := createUnwantedItems()
+	defer os.RemoveAll(source)
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	stats := &Stats{}
+
+	err := CopyFolder(source, dest, Options{}, stats)
+	assert.NoError(t, err)
+
+	_, err = os.Stat(filepath.Join(dest, ".Trash-1000"))
+	assert.True(t, os.IsNotExist(err), "trash folder should be skipped")
+	_, err = os.Stat(filepath.Join(dest, "core.1234"))
+	assert.True(t, os.IsNotExist(err), "core dump should be skipped")
+	_, err = os.Stat(filepath.Join(dest, "core.2024.txt"))
+	assert.NoError(t, err, "files named like core dumps that aren't should still be copied")
+	_, err = os.Stat(filepath.Join(dest, "testfile.txt"))
+	assert.NoError(t, err, "normal files should still be copied")
+	assert.Equal(t, 2, stats.DefaultSkipped)
+}
+
+func TestNoDefaultSkips(t *testing.T) {
+	source := createUnwantedItems()
+	defer os.RemoveAll(source)
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	stats := &Stats{}
+
+	err := CopyFolder(source, dest, Options{NoDefaultSkips: true}, stats)
+	assert.NoError(t, err)
+
+	_, err = os.Stat(filepath.Join(dest, ".Trash-1000"))
+	assert.NoError(t, err)
+	_, err = os.Stat(filepath.Join(dest, "core.1234"))
+	assert.NoError(t, err)
+	assert.Equal(t, 0, stats.DefaultSkipped)
+}
+
+func createUnwantedItems() (source string) {
+	source = createSource()
+	if err := os.Mkdir(filepath.Join(source, ".Trash-1000"), os.ModePerm); err != nil {
+		panic(err)
+	}
+	// ELF header of a little-endian 64-bit core file, up to e_type
+	makeTestFile(source, "core.1234", "\x7fELF\x02\x01\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x04\x00")
+	makeTestFile(source, "core.2024.txt", "notes from the core team")
+	makeTestFile(source, "testfile.txt", "backmeup susie")
+	return source
+}
@@ -0,0 +1,27 @@
+// +build !windows
+
+package dhcopy
+
+import (
+	"os"
+	"syscall"
+)
+
+// deviceID returns the ID of the device (filesystem) a file is on.
+func deviceID(info os.FileInfo) (uint64, bool) {
+	stat, ok := info.Sys().(*syscall.Stat_t)
+	if !ok {
+		return 0, false
+	}
+	return uint64(stat.Dev), true
+}
+
+// FileIDOf returns the device and inode of a file, which hardlinks to the
+// same contents share.
+func FileIDOf(info os.FileInfo) (FileID, bool) {
+	stat, ok := info.Sys().(*syscall.Stat_t)
+	if !ok {
+		return FileID{}, false
+	}
+	return FileID{uint64(stat.Dev), uint64(stat.Ino)}, true
+}
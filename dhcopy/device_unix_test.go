@@ -0,0 +1,41 @@
+// +build !windows
+
+package dhcopy
+
+import (
+	"github.com/stretchr/testify/assert"
+	"github.com/timabell/disk-hog-backup/test_helpers"
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestOneFileSystemCopiesSameFileSystem(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	if err := os.Mkdir(filepath.Join(source, "sub"), os.ModePerm); err != nil {
+		panic(err)
+	}
+	makeTestFile(filepath.Join(source, "sub"), "testfile.txt", "backmeup susie")
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+
+	err := CopyFolder(source, dest, Options{OneFileSystem: true}, &Stats{})
+	assert.NoError(t, err)
+
+	_, err = os.Stat(filepath.Join(dest, "sub", "testfile.txt"))
+	assert.NoError(t, err, "folders on the same filesystem should be copied")
+}
+
+func TestOnOtherFileSystem(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	options, err := Options{OneFileSystem: true}.forSource(source)
+	assert.NoError(t, err)
+	sameFs, err := os.Stat(source)
+	assert.NoError(t, err)
+
+	assert.False(t, options.onOtherFileSystem(sameFs))
+	options.sourceDevice++
+	assert.True(t, options.onOtherFileSystem(sameFs), "a different device ID should count as another filesystem")
+}
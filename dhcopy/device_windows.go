@@ -0,0 +1,15 @@
+package dhcopy
+
+import "os"
+
+// deviceID isn't available from os.FileInfo on Windows, so filesystem
+// boundaries aren't detected there.
+func deviceID(info os.FileInfo) (uint64, bool) {
+	return 0, false
+}
+
+// FileIDOf isn't available from os.FileInfo on Windows, so hardlinks aren't
+// spotted there.
+func FileIDOf(info os.FileInfo) (FileID, bool) {
+	return FileID{}, false
+}
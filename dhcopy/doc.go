@@ -0,0 +1,4 @@
+// Package dhcopy copies folders and files into a backup set, applying the
+// filtering, deduplication and symlink options, and collects the Stats that
+// are summarised at the end of a backup.
+package dhcopy
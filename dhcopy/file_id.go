@@ -0,0 +1,7 @@
+package dhcopy
+
+// FileID identifies a file's contents on disk, shared by all its hardlinks.
+type FileID struct {
+	Device uint64
+	Inode  uint64
+}
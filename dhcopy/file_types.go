@@ -0,0 +1,39 @@
+package dhcopy
+
+import (
+	"path/filepath"
+	"strings"
+)
+
+const otherFileType = "other"
+
+// fileTypes groups file extensions into broad categories for the stats
+// summary, so it's obvious what the backup space is being used by.
+var fileTypes = map[string][]string{
+	"images":    {"jpg", "jpeg", "png", "gif", "bmp", "tif", "tiff", "heic", "webp", "svg", "raw", "cr2", "nef", "dng"},
+	"video":     {"mp4", "mkv", "avi", "mov", "wmv", "webm", "m4v", "mpg", "mpeg"},
+	"audio":     {"mp3", "flac", "wav", "ogg", "m4a", "aac", "opus"},
+	"documents": {"pdf", "doc", "docx", "odt", "xls", "xlsx", "ods", "ppt", "pptx", "odp", "txt", "md", "rtf", "epub"},
+	"code":      {"go", "rs", "c", "h", "cpp", "cs", "java", "py", "rb", "js", "ts", "sh", "html", "css", "json", "xml", "yml", "yaml", "toml", "sql"},
+	"archives":  {"zip", "tar", "gz", "bz2", "xz", "7z", "rar", "iso"},
+}
+
+var fileTypesByExtension = indexFileTypes()
+
+func indexFileTypes() map[string]string {
+	index := map[string]string{}
+	for category, extensions := range fileTypes {
+		for _, extension := range extensions {
+			index[extension] = category
+		}
+	}
+	return index
+}
+
+func fileType(name string) string {
+	extension := strings.ToLower(strings.TrimPrefix(filepath.Ext(name), "."))
+	if category, found := fileTypesByExtension[extension]; found {
+		return category
+	}
+	return otherFileType
+}
@@ -0,0 +1,36 @@
+package dhcopy
+
+import (
+	"github.com/stretchr/testify/assert"
+	"testing"
+)
+
+func TestFileType(t *testing.T) {
+	assert.Equal(t, "images", fileType("holiday.JPG"))
+	assert.Equal(t, "documents", fileType("letter.odt"))
+	assert.Equal(t, "code", fileType("main.go"))
+	assert.Equal(t, otherFileType, fileType("Makefile"))
+	assert.Equal(t, otherFileType, fileType("data.unknown"))
+}
+
+func TestStatsSummarisesFileTypes(t *testing.T) {
+	stats := &Stats{}
+	stats.addCopied("a.jpg", 3*1024*1024)
+	stats.addCopied("b.png", 1024*1024)
+	stats.addCopied("c.txt", 10)
+
+	assert.Equal(t, []string{
+		"copied 3 files, 4.0 MB",
+		"  images: 2 files, 4.0 MB",
+		"  documents: 1 files, 10 B",
+	}, stats.Summary())
+}
+
+func TestStatsKeepsLargestFiles(t *testing.T) {
+	stats := &Stats{}
+	for _, file := range []CopiedFile{{"a", 10}, {"b", 30}, {"c", 20}, {"d", 5}, {"e", 30}} {
+		stats.addLargest(file, 3)
+	}
+
+	assert.Equal(t, []CopiedFile{{"b", 30}, {"e", 30}, {"c", 20}}, stats.Largest)
+}
@@ -0,0 +1,68 @@
+package dhcopy
+
+import (
+	"fmt"
+	"path/filepath"
+	"strings"
+)
+
+// FilterRule includes or excludes paths matching a pattern, like rsync's
+// --filter rules. Patterns are globs as for MatchPath. A leading / anchors
+// the pattern to the top of the source, otherwise it can match at any depth.
+// A trailing / only matches folders, and a trailing /*** matches a folder
+// and everything in it.
+type FilterRule struct {
+	Include bool
+	Pattern string
+}
+
+// ParseFilterRule parses a rule written as "+ pattern" to include or
+// "- pattern" to exclude.
+func ParseFilterRule(rule string) (FilterRule, error) {
+	if len(rule) < 3 || rule[1] != ' ' || (rule[0] != '+' && rule[0] != '-') {
+		return FilterRule{}, fmt.Errorf("invalid filter rule %q, expected '+ pattern' or '- pattern'", rule)
+	}
+	return FilterRule{Include: rule[0] == '+', Pattern: rule[2:]}, nil
+}
+
+func (r FilterRule) String() string {
+	if r.Include {
+		return "+ " + r.Pattern
+	}
+	return "- " + r.Pattern
+}
+
+// matches reports whether the rule applies to a slash separated path
+// relative to the source.
+func (r FilterRule) matches(relPath string, isDir bool) bool {
+	pattern := r.Pattern
+	if strings.HasSuffix(pattern, "/***") {
+		pattern = strings.TrimSuffix(pattern, "*") // ** matches the folder itself too
+	} else if strings.HasSuffix(pattern, "/") {
+		if !isDir {
+			return false
+		}
+		pattern = strings.TrimSuffix(pattern, "/")
+	}
+	if strings.HasPrefix(pattern, "/") {
+		pattern = strings.TrimPrefix(pattern, "/")
+	} else {
+		pattern = "**/" + pattern
+	}
+	return MatchPath(pattern, relPath)
+}
+
+// filteredOut reports whether the first filter rule that matches a path
+// excludes it. Paths that no rule matches are included.
+func (o Options) filteredOut(path string, isDir bool) bool {
+	if len(o.Filters) == 0 {
+		return false
+	}
+	relPath := filepath.ToSlash(o.relativePath(path))
+	for _, rule := range o.Filters {
+		if rule.matches(relPath, isDir) {
+			return !rule.Include
+		}
+	}
+	return false
+}
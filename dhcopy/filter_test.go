@@ -0,0 +1,76 @@
+package dhcopy
+
+import (
+	"github.com/stretchr/testify/assert"
+	"github.com/timabell/disk-hog-backup/test_helpers"
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestFilterOnlyOneFolder(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	for _, folder := range []string{"photos/2024", "music", "other/photos"} {
+		if err := os.MkdirAll(filepath.Join(source, folder), os.ModePerm); err != nil {
+			panic(err)
+		}
+	}
+	makeTestFile(filepath.Join(source, "photos", "2024"), "beach.jpg", "sand")
+	makeTestFile(filepath.Join(source, "photos"), "thumbs.db", "junk")
+	makeTestFile(filepath.Join(source, "music"), "song.mp3", "la la")
+	makeTestFile(source, "notes.txt", "x")
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	stats := &Stats{}
+
+	err := CopyFolder(source, dest, Options{Filters: []FilterRule{
+		{Include: false, Pattern: "thumbs.db"},
+		{Include: true, Pattern: "/photos/***"},
+		{Include: false, Pattern: "*"},
+	}}, stats)
+	assert.NoError(t, err)
+
+	_, err = os.Stat(filepath.Join(dest, "photos", "2024", "beach.jpg"))
+	assert.NoError(t, err, "included folder should be copied")
+	for _, excluded := range []string{"photos/thumbs.db", "music", "other", "notes.txt"} {
+		_, err = os.Stat(filepath.Join(dest, excluded))
+		assert.True(t, os.IsNotExist(err), "%v should be excluded", excluded)
+	}
+	assert.Equal(t, 4, stats.FilterSkipped)
+}
+
+func TestFilterRuleMatches(t *testing.T) {
+	for _, example := range []struct {
+		pattern string
+		path    string
+		isDir   bool
+		matches bool
+	}{
+		{"*.tmp", "a/b/c.tmp", false, true},
+		{"/*.tmp", "a/c.tmp", false, false},
+		{"/*.tmp", "c.tmp", false, true},
+		{"cache/", "home/cache", true, true},
+		{"cache/", "home/cache", false, false},
+		{"/photos/***", "photos", true, true},
+		{"/photos/***", "photos/2024/beach.jpg", false, true},
+		{"/photos/***", "old/photos/beach.jpg", false, false},
+		{"src/*.go", "project/src/main.go", false, true},
+	} {
+		rule := FilterRule{Pattern: example.pattern}
+		assert.Equal(t, example.matches, rule.matches(example.path, example.isDir), "%v against %v", example.pattern, example.path)
+	}
+}
+
+func TestParseFilterRule(t *testing.T) {
+	rule, err := ParseFilterRule("+ /photos/***")
+	assert.NoError(t, err)
+	assert.Equal(t, FilterRule{Include: true, Pattern: "/photos/***"}, rule)
+
+	rule, err = ParseFilterRule("- *.tmp")
+	assert.NoError(t, err)
+	assert.Equal(t, FilterRule{Include: false, Pattern: "*.tmp"}, rule)
+
+	_, err = ParseFilterRule("*.tmp")
+	assert.Error(t, err)
+}
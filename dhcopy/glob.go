@@ -0,0 +1,39 @@
+package dhcopy
+
+import (
+	"path"
+	"strings"
+)
+
+// MatchPath reports whether a slash separated path matches pattern. Each part of
+// the pattern is matched against one folder or file name with path.Match,
+// except ** which matches any number of folders, including none. An empty
+// pattern matches everything.
+func MatchPath(pattern string, name string) bool {
+	if pattern == "" {
+		return true
+	}
+	return matchParts(strings.Split(pattern, "/"), strings.Split(name, "/"))
+}
+
+func matchParts(pattern []string, name []string) bool {
+	for len(pattern) > 0 {
+		if pattern[0] == "**" {
+			for skip := 0; skip <= len(name); skip++ {
+				if matchParts(pattern[1:], name[skip:]) {
+					return true
+				}
+			}
+			return false
+		}
+		if len(name) == 0 {
+			return false
+		}
+		if matched, _ := path.Match(pattern[0], name[0]); !matched {
+			return false
+		}
+		pattern = pattern[1:]
+		name = name[1:]
+	}
+	return len(name) == 0
+}
@@ -0,0 +1,27 @@
+package dhcopy
+
+import (
+	"github.com/stretchr/testify/assert"
+	"testing"
+)
+
+func TestMatchPath(t *testing.T) {
+	for _, example := range []struct {
+		pattern string
+		name    string
+		matches bool
+	}{
+		{"", "anything/at/all.txt", true},
+		{"docs/letter.odt", "docs/letter.odt", true},
+		{"docs/*.odt", "docs/letter.odt", true},
+		{"docs/*.odt", "docs/2024/letter.odt", false},
+		{"docs/**/*.odt", "docs/letter.odt", true},
+		{"docs/**/*.odt", "docs/2024/june/letter.odt", true},
+		{"docs/**/*.odt", "photos/letter.odt", false},
+		{"**/letter.odt", "docs/2024/letter.odt", true},
+		{"docs/**", "docs/2024/letter.odt", true},
+		{"docs", "docs/letter.odt", false},
+	} {
+		assert.Equal(t, example.matches, MatchPath(example.pattern, example.name), "%v against %v", example.pattern, example.name)
+	}
+}
@@ -0,0 +1,22 @@
+package dhcopy
+
+import (
+	"crypto/md5"
+	"github.com/timabell/disk-hog-backup/dherrors"
+	"io"
+	"os"
+)
+
+// HashFile returns the MD5 hash of a file's contents.
+func HashFile(path string) ([]byte, error) {
+	file, err := os.Open(path)
+	if err != nil {
+		return nil, dherrors.Wrap(dherrors.OpRead, path, err)
+	}
+	defer file.Close()
+	hash := md5.New()
+	if _, err := io.Copy(hash, file); err != nil {
+		return nil, dherrors.Wrap(dherrors.OpRead, path, err)
+	}
+	return hash.Sum(nil), nil
+}
@@ -0,0 +1,20 @@
+package dhcopy
+
+import (
+	"encoding/hex"
+	"github.com/stretchr/testify/assert"
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestHashFile(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	makeTestFile(source, "testfile.txt", "backmeup susie")
+
+	hash, err := HashFile(filepath.Join(source, "testfile.txt"))
+
+	assert.NoError(t, err)
+	assert.Equal(t, "9f550269686b12954dae54a1ac743540", hex.EncodeToString(hash))
+}
@@ -0,0 +1,36 @@
+package dhcopy
+
+import (
+	"github.com/timabell/disk-hog-backup/backup_sets"
+	"os"
+	"path/filepath"
+)
+
+// Includes reports whether a file or folder at path within source, described
+// by info from os.Lstat, would be backed up with these options, so the source
+// can be compared with a set on the same terms. Only the rules that leave
+// things out are applied: filter rules, default skips, size limits, volatile
+// files when skipping them, other sets' metadata and special files, which are
+// never copied. Folders that aren't included shouldn't be descended into.
+func (o Options) Includes(source string, path string, info os.FileInfo) bool {
+	o.sourcePath = source
+	if !o.IncludeSetMetadata && !info.IsDir() && backup_sets.IsSetFolder(filepath.Base(filepath.Dir(path))) && backup_sets.IsMetadataFile(info.Name()) {
+		return false
+	}
+	if o.filteredOut(path, info.IsDir()) {
+		return false
+	}
+	if info.Mode()&os.ModeSymlink != 0 {
+		return true
+	}
+	if !o.NoDefaultSkips && isDefaultSkip(path, info) {
+		return false
+	}
+	if info.IsDir() {
+		return true
+	}
+	if isSpecialFile(info) || o.outsideSizeLimits(info.Size()) {
+		return false
+	}
+	return o.Volatile != VolatileSkip || !isVolatile(info.Name())
+}
@@ -0,0 +1,37 @@
+package dhcopy
+
+import (
+	"github.com/stretchr/testify/assert"
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestIncludes(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	if err := os.MkdirAll(filepath.Join(source, ".Trash"), os.ModePerm); err != nil {
+		panic(err)
+	}
+	makeTestFile(source, "photo.jpg", "a photo")
+	makeTestFile(source, "notes.txt", "notes")
+	makeTestFile(source, "swapfile", "swap")
+	makeTestFile(source, "huge.iso", "far too big")
+	options := Options{
+		Filters: []FilterRule{{Include: false, Pattern: "*.txt"}},
+		MaxSize: 8,
+	}
+
+	included := func(name string) bool {
+		path := filepath.Join(source, name)
+		info, err := os.Lstat(path)
+		assert.NoError(t, err)
+		return options.Includes(source, path, info)
+	}
+
+	assert.True(t, included("photo.jpg"))
+	assert.False(t, included("notes.txt"), "filtered out")
+	assert.False(t, included("swapfile"), "skipped by default")
+	assert.False(t, included(".Trash"), "skipped by default")
+	assert.False(t, included("huge.iso"), "over the size limit")
+}
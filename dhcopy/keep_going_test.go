@@ -0,0 +1,58 @@
+package dhcopy
+
+import (
+	"github.com/stretchr/testify/assert"
+	"github.com/timabell/disk-hog-backup/dherrors"
+	"github.com/timabell/disk-hog-backup/test_helpers"
+	"os"
+	"path/filepath"
+	"syscall"
+	"testing"
+)
+
+func TestKeepGoingCarriesOnAfterFailure(t *testing.T) {
+	source, dest := createBlockedCopy()
+	defer os.RemoveAll(source)
+	defer os.RemoveAll(dest)
+	stats := &Stats{}
+
+	err := CopyFolder(source, dest, Options{KeepGoing: true}, stats)
+
+	assert.NoError(t, err)
+	assert.Len(t, stats.Errors, 1)
+	_, err = os.Stat(filepath.Join(dest, "b.txt"))
+	assert.NoError(t, err, "files after the failure should still be copied")
+}
+
+func TestStopsAtFirstFailureByDefault(t *testing.T) {
+	source, dest := createBlockedCopy()
+	defer os.RemoveAll(source)
+	defer os.RemoveAll(dest)
+
+	err := CopyFolder(source, dest, Options{}, &Stats{})
+
+	assert.Error(t, err)
+}
+
+func TestReadOnlyDestinationStopsKeepGoing(t *testing.T) {
+	readOnly := dherrors.Wrap(dherrors.OpWrite, "/mnt/backups/file", &os.PathError{Op: "open", Path: "/mnt/backups/file", Err: syscall.EROFS})
+	stats := &Stats{}
+
+	err := keepGoing(readOnly, Options{KeepGoing: true}, stats)
+
+	assert.Equal(t, readOnly, err)
+	assert.Empty(t, stats.Errors)
+}
+
+// a folder in the way of a.txt in the destination makes copying it fail,
+// even when running as root
+func createBlockedCopy() (source string, dest string) {
+	source = createSource()
+	makeTestFile(source, "a.txt", "blocked")
+	makeTestFile(source, "b.txt", "fine")
+	dest = test_helpers.CreateTmpFolder(backupFolderName)
+	if err := os.Mkdir(filepath.Join(dest, "a.txt"), os.ModePerm); err != nil {
+		panic(err)
+	}
+	return source, dest
+}
@@ -0,0 +1,191 @@
+package dhcopy
+
+import (
+	"github.com/timabell/disk-hog-backup/dherrors"
+	"os"
+	"path/filepath"
+	"strings"
+	"time"
+)
+
+// Options controls how files are copied.
+type Options struct {
+	// BandwidthLimit caps how fast file contents are read, in bytes per
+	// second. Zero means unlimited.
+	BandwidthLimit int64
+
+	// ChunkSize is how many bytes are read and written at a time when copying
+	// file contents. Bigger chunks can be faster for large files on fast
+	// disks. Zero uses the Go default of 32 KB.
+	ChunkSize int
+
+	// StructureOnly recreates the folder tree without copying any files.
+	StructureOnly bool
+
+	// NoDefaultSkips includes swap files, core dumps, trash and lost+found,
+	// which are otherwise left out.
+	NoDefaultSkips bool
+
+	// ExactPaths copies only the paths given to CopyPaths, like rsync's
+	// --files-from without --recursive. Listed folders are created but what's
+	// in them is only copied if it's listed too.
+	ExactPaths bool
+
+	// KeepGoing logs files and folders that fail and records them in
+	// Stats.Errors instead of stopping at the first failure.
+	KeepGoing bool
+
+	// MaxDepth stops descending into folders more than this many levels
+	// below the source. The folders themselves are still created. Zero means
+	// no limit.
+	MaxDepth int
+
+	// IncludeSetMetadata copies disk-hog-backup's own files (like the error
+	// report) found in sets within the source, which are otherwise left out
+	// so that backing up an old backup drive doesn't mix them in with data.
+	IncludeSetMetadata bool
+
+	// Dedupe hardlinks files to an identical file already copied in the
+	// same backup, so duplicated or renamed content only takes space once.
+	Dedupe bool
+
+	// FollowSymlinks copies what symlinks point to instead of recreating the
+	// links themselves.
+	FollowSymlinks bool
+
+	// RelativeSymlinks rewrites absolute symlinks that point inside the
+	// source as relative links, so the set is self-contained rather than
+	// pointing back at the original files.
+	RelativeSymlinks bool
+
+	// OneFileSystem doesn't descend into folders that are on a different
+	// filesystem to the source, like mounted drives or /proc. The mount point
+	// folders themselves are still created.
+	OneFileSystem bool
+
+	// Filters are include/exclude rules applied in order to everything within
+	// the source, the first matching rule deciding. Excluded folders aren't
+	// descended into.
+	Filters []FilterRule
+
+	// MinSize and MaxSize leave out files smaller or bigger than this many
+	// bytes. Zero means no limit.
+	MinSize int64
+	MaxSize int64
+
+	// ChangedSince only copies files modified after this time, for quick
+	// top-up backups. The zero time copies everything.
+	ChangedSince time.Time
+
+	// Volatile says what to do with files that are likely to be changing as
+	// they're copied, like browser database journals and lock files.
+	Volatile VolatilePolicy
+
+	// FolderBreakdown totals up what was backed up by top level folder of the
+	// source, to help find what's taking up the space.
+	FolderBreakdown bool
+
+	// LargestFiles lists this many of the biggest files copied in the
+	// summary, to help spot things that shouldn't have been included.
+	LargestFiles int
+
+	// Retries is how many more times to try copying a file after a transient
+	// I/O error, like a network filesystem timing out. RetryDelay is the wait
+	// before the first retry, doubling for each one after.
+	Retries    int
+	RetryDelay time.Duration
+
+	// VerifyWrites reads back each file after copying it and checks the hash
+	// matches what was written.
+	VerifyWrites bool
+
+	// RecordSpecialFiles lists the FIFOs, sockets and device nodes that were
+	// skipped in Stats.SpecialFiles, so they can be recreated on restore.
+	RecordSpecialFiles bool
+
+	// details of the folder being backed up, filled in by forSource
+	sourcePath   string
+	sourceRoot   string
+	sourceDevice uint64
+
+	// paths written so far when the destination ignores the case of names,
+	// filled in by forDest
+	destNames caseCollisions
+}
+
+// forSource fills in details of the folder being backed up that are needed
+// while copying.
+func (o Options) forSource(source string) (Options, error) {
+	sourceRoot, err := filepath.Abs(source)
+	if err != nil {
+		return o, err
+	}
+	o.sourcePath = source
+	o.sourceRoot = sourceRoot
+	if o.OneFileSystem {
+		info, err := os.Stat(source)
+		if err != nil {
+			return o, dherrors.Wrap(dherrors.OpRead, source, err)
+		}
+		o.sourceDevice, _ = deviceID(info)
+	}
+	return o, nil
+}
+
+// forDest fills in details of the destination that are needed while
+// copying.
+func (o Options) forDest(dest string) (Options, error) {
+	caseInsensitive, err := isCaseInsensitive(dest)
+	if err != nil {
+		return o, err
+	}
+	if caseInsensitive {
+		o.destNames = caseCollisions{}
+	}
+	return o, nil
+}
+
+// checkCaseCollision returns an error if destPath would overwrite something
+// else written to a case-insensitive destination. It's only called for paths
+// that are about to be written, so things that are skipped can't clash.
+func (o Options) checkCaseCollision(destPath string) error {
+	if o.destNames == nil {
+		return nil
+	}
+	return o.destNames.check(destPath)
+}
+
+// outsideSizeLimits reports whether a file should be left out because of its
+// size.
+func (o Options) outsideSizeLimits(size int64) bool {
+	return (o.MinSize > 0 && size < o.MinSize) || (o.MaxSize > 0 && size > o.MaxSize)
+}
+
+// onOtherFileSystem reports whether a folder is on a different filesystem to
+// the source when that matters.
+func (o Options) onOtherFileSystem(folder os.FileInfo) bool {
+	if !o.OneFileSystem {
+		return false
+	}
+	device, ok := deviceID(folder)
+	return ok && device != o.sourceDevice
+}
+
+// topLevelFolder returns the folder directly within the source that path is
+// in, or "." for files at the top of the source.
+func (o Options) topLevelFolder(path string) string {
+	parts := strings.SplitN(o.relativePath(path), string(filepath.Separator), 2)
+	if len(parts) < 2 {
+		return "."
+	}
+	return parts[0]
+}
+
+// relativePath returns path relative to the source, for reporting.
+func (o Options) relativePath(path string) string {
+	relPath, err := filepath.Rel(o.sourcePath, path)
+	if err != nil {
+		return path
+	}
+	return relPath
+}
@@ -0,0 +1,24 @@
+package dhcopy
+
+import (
+	"github.com/timabell/disk-hog-backup/dherrors"
+	"log"
+	"time"
+)
+
+// retry runs attempt, trying again up to options.Retries times if it fails
+// with a transient error, waiting options.RetryDelay before the first retry
+// and twice as long before each one after that.
+func retry(attempt func() error, options Options, stats *Stats) error {
+	delay := options.RetryDelay
+	for tries := 0; ; tries++ {
+		err := attempt()
+		if err == nil || tries >= options.Retries || !dherrors.IsTransient(err) {
+			return err
+		}
+		log.Printf("retrying in %v after error: %s\n", delay, err)
+		stats.Retries++
+		time.Sleep(delay)
+		delay *= 2
+	}
+}
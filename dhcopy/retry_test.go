@@ -0,0 +1,53 @@
+package dhcopy
+
+import (
+	"github.com/stretchr/testify/assert"
+	"github.com/timabell/disk-hog-backup/dherrors"
+	"os"
+	"syscall"
+	"testing"
+	"time"
+)
+
+var transientErr = dherrors.Wrap(dherrors.OpRead, "/mnt/nas/file", &os.PathError{Op: "read", Path: "/mnt/nas/file", Err: syscall.EIO})
+
+func TestRetrySucceedsAfterTransientErrors(t *testing.T) {
+	attempts := 0
+	stats := &Stats{}
+
+	err := retry(func() error {
+		attempts++
+		if attempts < 3 {
+			return transientErr
+		}
+		return nil
+	}, Options{Retries: 3, RetryDelay: time.Millisecond}, stats)
+
+	assert.NoError(t, err)
+	assert.Equal(t, 3, attempts)
+	assert.Equal(t, 2, stats.Retries)
+}
+
+func TestRetryGivesUp(t *testing.T) {
+	attempts := 0
+
+	err := retry(func() error {
+		attempts++
+		return transientErr
+	}, Options{Retries: 2, RetryDelay: time.Millisecond}, &Stats{})
+
+	assert.Equal(t, transientErr, err)
+	assert.Equal(t, 3, attempts)
+}
+
+func TestRetryDoesNotRetryOtherErrors(t *testing.T) {
+	attempts := 0
+
+	err := retry(func() error {
+		attempts++
+		return os.ErrNotExist
+	}, Options{Retries: 2, RetryDelay: time.Millisecond}, &Stats{})
+
+	assert.Equal(t, os.ErrNotExist, err)
+	assert.Equal(t, 1, attempts)
+}
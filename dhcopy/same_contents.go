@@ -0,0 +1,57 @@
+package dhcopy
+
+import (
+	"bytes"
+	"github.com/timabell/disk-hog-backup/dherrors"
+	"io"
+	"os"
+)
+
+// SameContents compares two files byte for byte, for when a matching hash
+// isn't enough to go on, like before replacing one with a hardlink to the
+// other.
+func SameContents(path1 string, path2 string) (bool, error) {
+	file, err := os.Open(path1)
+	if err != nil {
+		return false, dherrors.Wrap(dherrors.OpRead, path1, err)
+	}
+	defer file.Close()
+	return sameContentsAs(file, path1, path2)
+}
+
+// sameContentsAs compares what's read from reader, which is the contents of
+// readerPath, with the file at path byte for byte.
+func sameContentsAs(reader io.Reader, readerPath string, path string) (bool, error) {
+	file, err := os.Open(path)
+	if err != nil {
+		return false, dherrors.Wrap(dherrors.OpRead, path, err)
+	}
+	defer file.Close()
+	buffer1 := make([]byte, 64*1024)
+	buffer2 := make([]byte, 64*1024)
+	for {
+		n1, err := readChunk(reader, buffer1)
+		if err != nil {
+			return false, dherrors.Wrap(dherrors.OpRead, readerPath, err)
+		}
+		n2, err := readChunk(file, buffer2)
+		if err != nil {
+			return false, dherrors.Wrap(dherrors.OpRead, path, err)
+		}
+		if !bytes.Equal(buffer1[:n1], buffer2[:n2]) {
+			return false, nil
+		}
+		if n1 < len(buffer1) {
+			return true, nil // both ended at the same place
+		}
+	}
+}
+
+// readChunk fills buffer unless the end of reader comes first.
+func readChunk(reader io.Reader, buffer []byte) (int, error) {
+	n, err := io.ReadFull(reader, buffer)
+	if err == io.EOF || err == io.ErrUnexpectedEOF {
+		err = nil
+	}
+	return n, err
+}
@@ -0,0 +1,29 @@
+package dhcopy
+
+import (
+	"github.com/stretchr/testify/assert"
+	"github.com/timabell/disk-hog-backup/test_helpers"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+func TestSameContents(t *testing.T) {
+	folder := test_helpers.CreateTmpFolder("same")
+	defer os.RemoveAll(folder)
+	big := strings.Repeat("x", 100*1024)
+	makeTestFile(folder, "big1", big+"a")
+	makeTestFile(folder, "big2", big+"a")
+	makeTestFile(folder, "big3", big+"b")
+	makeTestFile(folder, "longer", big+"ab")
+
+	for _, test := range []struct {
+		other string
+		same  bool
+	}{{"big2", true}, {"big3", false}, {"longer", false}} {
+		same, err := SameContents(filepath.Join(folder, "big1"), filepath.Join(folder, test.other))
+		assert.NoError(t, err)
+		assert.Equal(t, test.same, same, test.other)
+	}
+}
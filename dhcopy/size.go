@@ -0,0 +1,44 @@
+package dhcopy
+
+import (
+	"fmt"
+	"math"
+	"strconv"
+	"strings"
+)
+
+// FormatBytes returns a human readable size, e.g. "4.0 MB".
+func FormatBytes(bytes int64) string {
+	const unit = 1024
+	if bytes < unit {
+		return fmt.Sprintf("%v B", bytes)
+	}
+	div, exp := int64(unit), 0
+	for n := bytes / unit; n >= unit; n /= unit {
+		div *= unit
+		exp++
+	}
+	return fmt.Sprintf("%.1f %cB", float64(bytes)/float64(div), "KMGTPE"[exp])
+}
+
+// ParseSize parses a size like "500M" or "2G" in binary units, or a plain
+// number of bytes.
+func ParseSize(size string) (int64, error) {
+	number := strings.ToUpper(strings.TrimSpace(size))
+	number = strings.TrimSuffix(strings.TrimSuffix(number, "IB"), "B")
+	multiplier := int64(1)
+	if number != "" {
+		if i := strings.IndexByte("KMGTPE", number[len(number)-1]); i >= 0 {
+			multiplier = 1 << (10 * uint(i+1))
+			number = number[:len(number)-1]
+		}
+	}
+	n, err := strconv.ParseFloat(number, 64)
+	bytes := n * float64(multiplier)
+	// NaN fails every comparison, and infinity or anything too big for an
+	// int64 would come out as nonsense that turns size limits off
+	if err != nil || !(bytes >= 0 && bytes < math.MaxInt64) {
+		return 0, fmt.Errorf("invalid size %q, expected e.g. 500M or 2G", size)
+	}
+	return int64(bytes), nil
+}
@@ -0,0 +1,34 @@
+package dhcopy
+
+import (
+	"github.com/stretchr/testify/assert"
+	"testing"
+)
+
+func TestFormatBytes(t *testing.T) {
+	assert.Equal(t, "10 B", FormatBytes(10))
+	assert.Equal(t, "1.5 KB", FormatBytes(1536))
+	assert.Equal(t, "4.0 MB", FormatBytes(4*1024*1024))
+}
+
+func TestParseSize(t *testing.T) {
+	for size, expected := range map[string]int64{
+		"1000":   1000,
+		"10B":    10,
+		"500k":   500 * 1024,
+		"500KiB": 500 * 1024,
+		"1.5M":   1536 * 1024,
+		"2G":     2 * 1024 * 1024 * 1024,
+	} {
+		parsed, err := ParseSize(size)
+		assert.NoError(t, err, size)
+		assert.Equal(t, expected, parsed, size)
+	}
+}
+
+func TestParseSizeRejectsNonsense(t *testing.T) {
+	for _, size := range []string{"", "big", "-1M", "M", "inf", "-inf", "nan", "9E", "1e30"} {
+		_, err := ParseSize(size)
+		assert.Error(t, err, size)
+	}
+}
@@ -0,0 +1,54 @@
+package dhcopy
+
+import (
+	"log"
+	"os"
+)
+
+// SpecialFile is a FIFO, socket or device node found in the source. They
+// can't be copied, but their details can be recorded so they can be recreated
+// with mkfifo or mknod.
+type SpecialFile struct {
+	// Path is relative to the source.
+	Path string
+
+	// Type is fifo, socket, char or block.
+	Type string
+
+	Mode os.FileMode
+
+	// Major and Minor are the device numbers of device nodes, where known.
+	Major uint32
+	Minor uint32
+}
+
+func isSpecialFile(info os.FileInfo) bool {
+	return !info.IsDir() && !info.Mode().IsRegular() && info.Mode()&os.ModeSymlink == 0
+}
+
+// skipSpecialFile counts a special file in stats, recording its details if
+// options say to.
+func skipSpecialFile(source string, info os.FileInfo, options Options, stats *Stats) {
+	log.Printf("skipping special file %v\n", source)
+	stats.SpecialSkipped++
+	if !options.RecordSpecialFiles {
+		return
+	}
+	special := SpecialFile{Path: options.relativePath(source), Type: specialFileType(info.Mode()), Mode: info.Mode().Perm()}
+	special.Major, special.Minor, _ = deviceNumbers(info)
+	stats.SpecialFiles = append(stats.SpecialFiles, special)
+}
+
+func specialFileType(mode os.FileMode) string {
+	switch {
+	case mode&os.ModeNamedPipe != 0:
+		return "fifo"
+	case mode&os.ModeSocket != 0:
+		return "socket"
+	case mode&os.ModeCharDevice != 0:
+		return "char"
+	case mode&os.ModeDevice != 0:
+		return "block"
+	}
+	return "other"
+}
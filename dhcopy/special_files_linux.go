@@ -0,0 +1,19 @@
+package dhcopy
+
+import (
+	"os"
+	"syscall"
+)
+
+// deviceNumbers returns the major and minor numbers of a device node, decoded
+// from Linux's dev_t.
+func deviceNumbers(info os.FileInfo) (major uint32, minor uint32, ok bool) {
+	stat, ok := info.Sys().(*syscall.Stat_t)
+	if !ok || info.Mode()&os.ModeDevice == 0 {
+		return 0, 0, false
+	}
+	rdev := uint64(stat.Rdev)
+	major = uint32((rdev>>8)&0xfff) | uint32((rdev>>32)&^0xfff)
+	minor = uint32(rdev&0xff) | uint32((rdev>>12)&^0xff)
+	return major, minor, true
+}
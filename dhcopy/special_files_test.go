@@ -0,0 +1,32 @@
+// +build linux
+
+package dhcopy
+
+import (
+	"github.com/stretchr/testify/assert"
+	"github.com/timabell/disk-hog-backup/test_helpers"
+	"os"
+	"path/filepath"
+	"syscall"
+	"testing"
+)
+
+func TestSkipsFifos(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	if err := syscall.Mkfifo(filepath.Join(source, "pipe"), 0600); err != nil {
+		panic(err)
+	}
+	makeTestFile(source, "testfile.txt", "backmeup susie")
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	stats := &Stats{}
+
+	err := CopyFolder(source, dest, Options{RecordSpecialFiles: true}, stats)
+	assert.NoError(t, err)
+
+	_, err = os.Lstat(filepath.Join(dest, "pipe"))
+	assert.True(t, os.IsNotExist(err), "fifo shouldn't be copied")
+	assert.Equal(t, 1, stats.SpecialSkipped)
+	assert.Equal(t, []SpecialFile{{Path: "pipe", Type: "fifo", Mode: 0600}}, stats.SpecialFiles)
+}
@@ -0,0 +1,213 @@
+package dhcopy
+
+import (
+	"fmt"
+	"sort"
+)
+
+// Stats counts what happened while copying, for the summary at the end of a
+// backup.
+type Stats struct {
+	FilesCopied    int
+	BytesCopied    int64
+	SymlinksCopied int
+
+	// FileTypes breaks down the files copied by category, e.g. "images".
+	FileTypes map[string]*Total
+
+	// Folders breaks down the files backed up by top level folder of the
+	// source, including those hardlinked as duplicates, when
+	// Options.FolderBreakdown is on.
+	Folders map[string]*Total
+
+	// Largest lists the biggest files copied, biggest first, when
+	// Options.LargestFiles is set.
+	Largest []CopiedFile
+
+	// VerifiedFiles counts files read back and checked after copying, when
+	// Options.VerifyWrites is on.
+	VerifiedFiles int
+
+	// DedupedFiles and DedupedBytes count files hardlinked to an identical
+	// file already copied in this backup instead of being copied again.
+	DedupedFiles int
+	DedupedBytes int64
+
+	// DefaultSkipped counts items left out by the built-in skip list.
+	DefaultSkipped int
+
+	// FilterSkipped counts items excluded by Options.Filters.
+	FilterSkipped int
+
+	// DanglingSymlinks counts symlinks to things that don't exist, which are
+	// skipped when following symlinks.
+	DanglingSymlinks int
+
+	// SizeSkipped counts files left out by the size limits.
+	SizeSkipped int
+
+	// UnchangedSkipped counts files left out by Options.ChangedSince.
+	UnchangedSkipped int
+
+	// SpecialSkipped counts FIFOs, sockets and device nodes, which can't be
+	// copied. SpecialFiles lists them when Options.RecordSpecialFiles is on.
+	SpecialSkipped int
+	SpecialFiles   []SpecialFile
+
+	// VolatileCopied and VolatileSkipped count files that were likely to be
+	// changing as they were copied, see Options.Volatile.
+	VolatileCopied  int
+	VolatileSkipped int
+
+	// TimesNotKept counts files copied whose modification time couldn't be
+	// set to match the source.
+	TimesNotKept int
+
+	// Retries counts attempts to copy files again after transient errors.
+	Retries int
+
+	// Errors lists what couldn't be backed up when running with KeepGoing.
+	Errors []error
+
+	// where contents were copied to, for finding duplicates
+	copiedSizes   map[int64]bool
+	copiedContent map[contentKey]string
+}
+
+// Total is a count of files and their combined size.
+type Total struct {
+	Files int
+	Bytes int64
+}
+
+func (s *Stats) addCopied(name string, size int64) {
+	s.FilesCopied++
+	s.BytesCopied += size
+	if s.FileTypes == nil {
+		s.FileTypes = map[string]*Total{}
+	}
+	category := fileType(name)
+	if s.FileTypes[category] == nil {
+		s.FileTypes[category] = &Total{}
+	}
+	s.FileTypes[category].Files++
+	s.FileTypes[category].Bytes += size
+}
+
+// CopiedFile is a file copied into a set, with its path relative to the
+// source.
+type CopiedFile struct {
+	Path string
+	Size int64
+}
+
+// addLargest keeps track of the limit biggest files copied.
+func (s *Stats) addLargest(file CopiedFile, limit int) {
+	i := sort.Search(len(s.Largest), func(i int) bool { return s.Largest[i].Size < file.Size })
+	if i >= limit {
+		return
+	}
+	s.Largest = append(s.Largest, CopiedFile{})
+	copy(s.Largest[i+1:], s.Largest[i:])
+	s.Largest[i] = file
+	if len(s.Largest) > limit {
+		s.Largest = s.Largest[:limit]
+	}
+}
+
+func (s *Stats) addFolderTotal(folder string, size int64) {
+	if s.Folders == nil {
+		s.Folders = map[string]*Total{}
+	}
+	if s.Folders[folder] == nil {
+		s.Folders[folder] = &Total{}
+	}
+	s.Folders[folder].Files++
+	s.Folders[folder].Bytes += size
+}
+
+// Skipped returns the total number of items left out on purpose, by the
+// default skips, filters, size limits and so on.
+func (s *Stats) Skipped() int {
+	return s.DefaultSkipped + s.FilterSkipped + s.DanglingSymlinks + s.SizeSkipped + s.UnchangedSkipped + s.SpecialSkipped + s.VolatileSkipped
+}
+
+// Summary returns lines describing the stats, to be logged after a backup.
+func (s *Stats) Summary() []string {
+	lines := []string{fmt.Sprintf("copied %v files, %v", s.FilesCopied, FormatBytes(s.BytesCopied))}
+	for _, category := range sortedBySize(s.FileTypes) {
+		total := s.FileTypes[category]
+		lines = append(lines, fmt.Sprintf("  %v: %v files, %v", category, total.Files, FormatBytes(total.Bytes)))
+	}
+	if len(s.Folders) > 0 {
+		lines = append(lines, "backed up by top level folder:")
+		for _, folder := range sortedBySize(s.Folders) {
+			total := s.Folders[folder]
+			lines = append(lines, fmt.Sprintf("  %v: %v files, %v", folder, total.Files, FormatBytes(total.Bytes)))
+		}
+	}
+	if len(s.Largest) > 0 {
+		lines = append(lines, "largest files copied:")
+		for _, file := range s.Largest {
+			lines = append(lines, fmt.Sprintf("  %v: %v", file.Path, FormatBytes(file.Size)))
+		}
+	}
+	if s.VerifiedFiles > 0 {
+		lines = append(lines, fmt.Sprintf("verified %v files by reading them back", s.VerifiedFiles))
+	}
+	if s.SymlinksCopied > 0 {
+		lines = append(lines, fmt.Sprintf("recreated %v symlinks", s.SymlinksCopied))
+	}
+	if s.DedupedFiles > 0 {
+		lines = append(lines, fmt.Sprintf("hardlinked %v duplicate files, saving %v", s.DedupedFiles, FormatBytes(s.DedupedBytes)))
+	}
+	if s.DefaultSkipped > 0 {
+		lines = append(lines, fmt.Sprintf("skipped %v swap files, core dumps, trash and lost+found items (see -no-default-skips)", s.DefaultSkipped))
+	}
+	if s.FilterSkipped > 0 {
+		lines = append(lines, fmt.Sprintf("skipped %v items excluded by -filter rules", s.FilterSkipped))
+	}
+	if s.DanglingSymlinks > 0 {
+		lines = append(lines, fmt.Sprintf("skipped %v symlinks to things that don't exist", s.DanglingSymlinks))
+	}
+	if s.SizeSkipped > 0 {
+		lines = append(lines, fmt.Sprintf("skipped %v files outside the size limits", s.SizeSkipped))
+	}
+	if s.UnchangedSkipped > 0 {
+		lines = append(lines, fmt.Sprintf("skipped %v files not modified since the cutoff", s.UnchangedSkipped))
+	}
+	if s.SpecialSkipped > 0 {
+		lines = append(lines, fmt.Sprintf("skipped %v FIFOs, sockets and device nodes", s.SpecialSkipped))
+	}
+	if s.VolatileCopied > 0 {
+		lines = append(lines, fmt.Sprintf("copied %v files that were probably in use and may be inconsistent, like browser database journals and lock files (see -volatile)", s.VolatileCopied))
+	}
+	if s.VolatileSkipped > 0 {
+		lines = append(lines, fmt.Sprintf("skipped %v files that were probably in use, like browser database journals and lock files", s.VolatileSkipped))
+	}
+	if s.TimesNotKept > 0 {
+		lines = append(lines, fmt.Sprintf("couldn't keep the modification time of %v files, comparing them with the source will mean reading them", s.TimesNotKept))
+	}
+	if s.Retries > 0 {
+		lines = append(lines, fmt.Sprintf("retried %v times after transient I/O errors", s.Retries))
+	}
+	if len(s.Errors) > 0 {
+		lines = append(lines, fmt.Sprintf("%v files or folders couldn't be backed up", len(s.Errors)))
+	}
+	return lines
+}
+
+// sortedBySize returns the keys of totals, biggest first.
+func sortedBySize(totals map[string]*Total) []string {
+	keys := make([]string, 0, len(totals))
+	for key := range totals {
+		keys = append(keys, key)
+	}
+	sort.Slice(keys, func(i, j int) bool {
+		if totals[keys[i]].Bytes != totals[keys[j]].Bytes {
+			return totals[keys[i]].Bytes > totals[keys[j]].Bytes
+		}
+		return keys[i] < keys[j]
+	})
+	return keys
+}
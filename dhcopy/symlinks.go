@@ -0,0 +1,57 @@
+package dhcopy
+
+import (
+	"github.com/timabell/disk-hog-backup/dherrors"
+	"log"
+	"os"
+	"path/filepath"
+)
+
+// copySymlink recreates the symlink at source as dest, pointing at the same
+// target.
+func copySymlink(source string, dest string, options Options, stats *Stats) error {
+	target, err := os.Readlink(source)
+	if err != nil {
+		return dherrors.Wrap(dherrors.OpRead, source, err)
+	}
+	if options.RelativeSymlinks {
+		target, err = relativeTarget(source, target, options.sourceRoot)
+		if err != nil {
+			return dherrors.Wrap(dherrors.OpRead, source, err)
+		}
+	}
+	log.Printf("linking %v to %v\n", dest, target)
+	if err := os.Symlink(target, dest); err != nil {
+		return dherrors.Wrap(dherrors.OpLink, dest, err)
+	}
+	stats.SymlinksCopied++
+	return nil
+}
+
+// relativeTarget rewrites an absolute symlink target inside root as a path
+// relative to the link. Other targets are returned unchanged.
+func relativeTarget(link string, target string, root string) (string, error) {
+	if !filepath.IsAbs(target) {
+		return target, nil
+	}
+	if _, err := relativeToSource(root, target); err != nil {
+		return target, nil // points outside the source
+	}
+	linkFolder, err := filepath.Abs(filepath.Dir(link))
+	if err != nil {
+		return "", err
+	}
+	return filepath.Rel(linkFolder, target)
+}
+
+// isAncestor reports whether folder is one of ancestors, i.e. a symlink to it
+// would loop back on itself. Compares device and inode so it works whatever
+// path the link takes.
+func isAncestor(folder os.FileInfo, ancestors []os.FileInfo) bool {
+	for _, ancestor := range ancestors {
+		if os.SameFile(folder, ancestor) {
+			return true
+		}
+	}
+	return false
+}
@@ -0,0 +1,92 @@
+package dhcopy
+
+import (
+	"github.com/stretchr/testify/assert"
+	"github.com/timabell/disk-hog-backup/test_helpers"
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestRecreatesSymlinks(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	makeTestFile(source, "testfile.txt", "backmeup susie")
+	test_helpers.MakeSymlink("testfile.txt", filepath.Join(source, "link.txt"))
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	stats := &Stats{}
+
+	err := CopyFolder(source, dest, Options{}, stats)
+	assert.NoError(t, err)
+
+	target, err := os.Readlink(filepath.Join(dest, "link.txt"))
+	assert.NoError(t, err, "symlink should be copied as a symlink")
+	assert.Equal(t, "testfile.txt", target)
+	assert.Equal(t, 1, stats.SymlinksCopied)
+}
+
+func TestFollowSymlinks(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	outside := test_helpers.CreateTmpFolder("outside")
+	defer os.RemoveAll(outside)
+	makeTestFile(outside, "elsewhere.txt", "backmeup susie")
+	test_helpers.MakeSymlink(outside, filepath.Join(source, "linked-folder"))
+	test_helpers.MakeSymlink(source, filepath.Join(source, "loop"))
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+
+	err := CopyFolder(source, dest, Options{FollowSymlinks: true}, &Stats{})
+	assert.NoError(t, err)
+
+	info, err := os.Lstat(filepath.Join(dest, "linked-folder", "elsewhere.txt"))
+	assert.NoError(t, err, "contents of linked folder should be copied")
+	if err == nil {
+		assert.True(t, info.Mode().IsRegular())
+	}
+	_, err = os.Lstat(filepath.Join(dest, "loop"))
+	assert.True(t, os.IsNotExist(err), "link back to the source should be skipped")
+}
+
+func TestFollowSymlinksSkipsDanglingLinks(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	makeTestFile(source, "testfile.txt", "backmeup susie")
+	test_helpers.MakeSymlink("missing.txt", filepath.Join(source, "dangling"))
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	stats := &Stats{}
+
+	err := CopyFolder(source, dest, Options{FollowSymlinks: true}, stats)
+	assert.NoError(t, err, "a dangling link shouldn't stop the backup")
+
+	assert.Equal(t, 1, stats.DanglingSymlinks)
+	_, err = os.Lstat(filepath.Join(dest, "dangling"))
+	assert.True(t, os.IsNotExist(err))
+	_, err = os.Stat(filepath.Join(dest, "testfile.txt"))
+	assert.NoError(t, err)
+}
+
+func TestRelativeSymlinks(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	makeTestFile(source, "testfile.txt", "backmeup susie")
+	if err := os.Mkdir(filepath.Join(source, "sub"), os.ModePerm); err != nil {
+		panic(err)
+	}
+	test_helpers.MakeSymlink(filepath.Join(source, "testfile.txt"), filepath.Join(source, "sub", "inside.txt"))
+	test_helpers.MakeSymlink("/etc/hostname", filepath.Join(source, "outside.txt"))
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+
+	err := CopyFolder(source, dest, Options{RelativeSymlinks: true}, &Stats{})
+	assert.NoError(t, err)
+
+	target, err := os.Readlink(filepath.Join(dest, "sub", "inside.txt"))
+	assert.NoError(t, err)
+	assert.Equal(t, filepath.Join("..", "testfile.txt"), target, "link inside the source should be made relative")
+	target, err = os.Readlink(filepath.Join(dest, "outside.txt"))
+	assert.NoError(t, err)
+	assert.Equal(t, "/etc/hostname", target, "link outside the source should be left alone")
+}
@@ -0,0 +1,32 @@
+package dhcopy
+
+import (
+	"io"
+	"time"
+)
+
+// throttledReader sleeps between reads so that the average rate since the
+// first read stays at or below bytesPerSec.
+type throttledReader struct {
+	reader      io.Reader
+	bytesPerSec int64
+	start       time.Time
+	bytesRead   int64
+}
+
+func newThrottledReader(reader io.Reader, bytesPerSec int64) *throttledReader {
+	return &throttledReader{reader: reader, bytesPerSec: bytesPerSec, start: time.Now()}
+}
+
+func (r *throttledReader) Read(p []byte) (int, error) {
+	if int64(len(p)) > r.bytesPerSec {
+		p = p[:r.bytesPerSec]
+	}
+	n, err := r.reader.Read(p)
+	r.bytesRead += int64(n)
+	due := time.Duration(float64(r.bytesRead) / float64(r.bytesPerSec) * float64(time.Second))
+	if wait := due - time.Since(r.start); wait > 0 {
+		time.Sleep(wait)
+	}
+	return n, err
+}
@@ -0,0 +1,20 @@
+package dhcopy
+
+import (
+	"bytes"
+	"github.com/stretchr/testify/assert"
+	"io/ioutil"
+	"testing"
+	"time"
+)
+
+func TestThrottledReaderLimitsRate(t *testing.T) {
+	data := bytes.Repeat([]byte("x"), 200)
+	start := time.Now()
+
+	read, err := ioutil.ReadAll(newThrottledReader(bytes.NewReader(data), 1000))
+
+	assert.NoError(t, err)
+	assert.Equal(t, data, read, "throttling shouldn't change the data")
+	assert.True(t, time.Since(start) >= 180*time.Millisecond, "200 bytes at 1000 bytes/sec should take about 200ms")
+}
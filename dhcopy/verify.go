@@ -0,0 +1,23 @@
+package dhcopy
+
+import (
+	"bytes"
+	"fmt"
+	"github.com/timabell/disk-hog-backup/dherrors"
+)
+
+// verifyCopy re-reads a file that has just been written and checks it has the
+// expected hash. The read may well be served from the OS's cache rather than
+// the disk itself, so this catches data going astray on the way to the
+// destination filesystem rather than the drive failing later.
+func verifyCopy(dest string, expectedHash []byte, stats *Stats) error {
+	hash, err := HashFile(dest)
+	if err != nil {
+		return err
+	}
+	if !bytes.Equal(hash, expectedHash) {
+		return dherrors.Wrap(dherrors.OpWrite, dest, fmt.Errorf("contents read back don't match what was written (md5 %x, expected %x)", hash, expectedHash))
+	}
+	stats.VerifiedFiles++
+	return nil
+}
@@ -0,0 +1,35 @@
+package dhcopy
+
+import (
+	"github.com/stretchr/testify/assert"
+	"github.com/timabell/disk-hog-backup/test_helpers"
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestVerifyWrites(t *testing.T) {
+	source := createSource()
+	defer os.RemoveAll(source)
+	makeTestFile(source, "testfile.txt", "backmeup susie")
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	stats := &Stats{}
+
+	err := CopyFolder(source, dest, Options{VerifyWrites: true}, stats)
+
+	assert.NoError(t, err)
+	assert.Equal(t, 1, stats.VerifiedFiles)
+}
+
+func TestVerifyCopySpotsMismatch(t *testing.T) {
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	makeTestFile(dest, "testfile.txt", "backmeup susie")
+	stats := &Stats{}
+
+	err := verifyCopy(filepath.Join(dest, "testfile.txt"), []byte("not the right hash"), stats)
+
+	assert.Error(t, err)
+	assert.Equal(t, 0, stats.VerifiedFiles)
+}
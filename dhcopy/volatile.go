@@ -0,0 +1,61 @@
+package dhcopy
+
+import (
+	"fmt"
+	"path/filepath"
+)
+
+// Files that are rewritten constantly while the program using them is
+// running, so a copy taken while it's open is likely to be inconsistent:
+// SQLite journals from browsers and other apps, Outlook's offline store and
+// lock files. Matched against each file name with filepath.Match.
+var volatileFiles = []string{
+	"*-wal",
+	"*-shm",
+	"*-journal",
+	"*.ost",
+	"lock",
+	"LOCK",
+	"parent.lock",
+	".parentlock",
+	"~$*",
+	".~lock.*#",
+}
+
+// VolatilePolicy says what to do with files that are likely to be in use and
+// changing while they're copied.
+type VolatilePolicy int
+
+const (
+	// VolatileWarn copies them but logs a warning and counts them separately.
+	VolatileWarn VolatilePolicy = iota
+
+	// VolatileSkip leaves them out.
+	VolatileSkip
+
+	// VolatileCopy treats them like any other file.
+	VolatileCopy
+)
+
+// ParseVolatilePolicy parses the command line names of the policies: warn,
+// skip or copy.
+func ParseVolatilePolicy(name string) (VolatilePolicy, error) {
+	switch name {
+	case "warn":
+		return VolatileWarn, nil
+	case "skip":
+		return VolatileSkip, nil
+	case "copy":
+		return VolatileCopy, nil
+	}
+	return VolatileWarn, fmt.Errorf("unknown volatile file policy %q, expected warn, skip or copy", name)
+}
+
+func isVolatile(name string) bool {
+	for _, pattern := range volatileFiles {
+		if matched, _ := filepath.Match(pattern, name); matched {
+			return true
+		}
+	}
+	return false
+}
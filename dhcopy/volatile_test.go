@@ -0,0 +1,72 @@
+package dhcopy
+
+import (
+	"github.com/stretchr/testify/assert"
+	"github.com/timabell/disk-hog-backup/test_helpers"
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestVolatileFilesAreCopiedWithWarningByDefault(t *testing.T) {
+	source := createVolatileFiles()
+	defer os.RemoveAll(source)
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	stats := &Stats{}
+
+	err := CopyFolder(source, dest, Options{}, stats)
+	assert.NoError(t, err)
+
+	_, err = os.Stat(filepath.Join(dest, "places.sqlite-wal"))
+	assert.NoError(t, err)
+	assert.Equal(t, 1, stats.VolatileCopied)
+	assert.Equal(t, 2, stats.FilesCopied)
+}
+
+func TestVolatileSkip(t *testing.T) {
+	source := createVolatileFiles()
+	defer os.RemoveAll(source)
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	stats := &Stats{}
+
+	err := CopyFolder(source, dest, Options{Volatile: VolatileSkip}, stats)
+	assert.NoError(t, err)
+
+	_, err = os.Stat(filepath.Join(dest, "places.sqlite-wal"))
+	assert.True(t, os.IsNotExist(err), "volatile file should be skipped")
+	_, err = os.Stat(filepath.Join(dest, "places.sqlite"))
+	assert.NoError(t, err, "the database itself should still be copied")
+	assert.Equal(t, 1, stats.VolatileSkipped)
+}
+
+func TestVolatileCopy(t *testing.T) {
+	source := createVolatileFiles()
+	defer os.RemoveAll(source)
+	dest := test_helpers.CreateTmpFolder(backupFolderName)
+	defer os.RemoveAll(dest)
+	stats := &Stats{}
+
+	err := CopyFolder(source, dest, Options{Volatile: VolatileCopy}, stats)
+	assert.NoError(t, err)
+
+	assert.Equal(t, 0, stats.VolatileCopied)
+	assert.Equal(t, 2, stats.FilesCopied)
+}
+
+func TestParseVolatilePolicy(t *testing.T) {
+	policy, err := ParseVolatilePolicy("skip")
+	assert.NoError(t, err)
+	assert.Equal(t, VolatileSkip, policy)
+
+	_, err = ParseVolatilePolicy("snapshot")
+	assert.Error(t, err)
+}
+
+func createVolatileFiles() (source string) {
+	source = test_helpers.CreateTmpFolder("orig")
+	makeTestFile(source, "places.sqlite", "bookmarks")
+	makeTestFile(source, "places.sqlite-wal", "half written")
+	return source
+}
@@ -0,0 +1,4 @@
+// Package dherrors wraps errors with the operation and path that failed, so
+// callers can tell read failures in the source from write failures in the
+// destination.
+package dherrors
@@ -0,0 +1,60 @@
+package dherrors
+
+import (
+	"errors"
+	"fmt"
+	"os"
+	"syscall"
+)
+
+// Op is the kind of filesystem operation that failed.
+type Op string
+
+const (
+	OpRead     Op = "read"
+	OpWrite    Op = "write"
+	OpLink     Op = "link"
+	OpMetadata Op = "metadata"
+)
+
+// Error records which path a backup failed on and what it was doing there,
+// so callers can tell a source read problem from a destination write problem.
+type Error struct {
+	Op   Op
+	Path string
+	Err  error
+}
+
+// Wrap adds operation and path context to err, returning nil if err is nil.
+func Wrap(op Op, path string, err error) error {
+	if err == nil {
+		return nil
+	}
+	return &Error{Op: op, Path: path, Err: err}
+}
+
+func (e *Error) Error() string {
+	cause := e.Err
+	var pathErr *os.PathError
+	if errors.As(cause, &pathErr) && pathErr.Path == e.Path {
+		cause = pathErr.Err // don't repeat the path
+	}
+	return fmt.Sprintf("%s failed on %v: %v", e.Op, e.Path, cause)
+}
+
+func (e *Error) Unwrap() error {
+	return e.Err
+}
+
+// IsReadOnly reports whether err is because the filesystem is mounted
+// read-only, in which case every further write will fail too.
+func IsReadOnly(err error) bool {
+	return errors.Is(err, syscall.EROFS)
+}
+
+// IsTransient reports whether err is the kind of I/O error that network
+// filesystems and USB disks that have gone to sleep produce, and which may
+// go away if the operation is tried again.
+func IsTransient(err error) bool {
+	return errors.Is(err, syscall.EIO) || errors.Is(err, syscall.ETIMEDOUT) || errors.Is(err, syscall.EAGAIN)
+}
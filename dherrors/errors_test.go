@@ -0,0 +1,46 @@
+package dherrors
+
+import (
+	"errors"
+	"github.com/stretchr/testify/assert"
+	"os"
+	"syscall"
+	"testing"
+)
+
+func TestWrapNil(t *testing.T) {
+	assert.NoError(t, Wrap(OpRead, "/some/file", nil))
+}
+
+func TestMessageIncludesOperationAndPath(t *testing.T) {
+	_, openErr := os.Open("/no/such/file")
+
+	err := Wrap(OpRead, "/no/such/file", openErr)
+
+	assert.Equal(t, "read failed on /no/such/file: "+errors.Unwrap(openErr).Error(), err.Error())
+}
+
+func TestMatchesUnderlyingError(t *testing.T) {
+	_, openErr := os.Open("/no/such/file")
+
+	err := Wrap(OpRead, "/no/such/file", openErr)
+
+	assert.True(t, errors.Is(err, os.ErrNotExist))
+	var dhErr *Error
+	assert.True(t, errors.As(err, &dhErr))
+	assert.Equal(t, OpRead, dhErr.Op)
+}
+
+func TestIsReadOnly(t *testing.T) {
+	readOnly := Wrap(OpWrite, "/mnt/backups", &os.PathError{Op: "mkdir", Path: "/mnt/backups", Err: syscall.EROFS})
+
+	assert.True(t, IsReadOnly(readOnly))
+	assert.False(t, IsReadOnly(Wrap(OpWrite, "/mnt/backups", os.ErrPermission)))
+}
+
+func TestIsTransient(t *testing.T) {
+	timedOut := Wrap(OpRead, "/mnt/nas/file", &os.PathError{Op: "read", Path: "/mnt/nas/file", Err: syscall.ETIMEDOUT})
+
+	assert.True(t, IsTransient(timedOut))
+	assert.False(t, IsTransient(Wrap(OpRead, "/mnt/nas/file", os.ErrNotExist)))
+}
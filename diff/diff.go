@@ -0,0 +1,171 @@
+package diff
+
+import (
+	"bytes"
+	"fmt"
+	"github.com/timabell/disk-hog-backup/backup_sets"
+	"github.com/timabell/disk-hog-backup/dhcopy"
+	"github.com/timabell/disk-hog-backup/dherrors"
+	"io"
+	"os"
+	"path/filepath"
+	"sort"
+)
+
+// Changes lists files that differ between two folders, as paths relative to
+// the folders being compared.
+type Changes struct {
+	Added   []string `json:"added"`
+	Removed []string `json:"removed"`
+	Changed []string `json:"changed"`
+}
+
+// SchemaVersion is output as schema_version alongside Changes in JSON, see
+// "JSON output" in the README.
+const SchemaVersion = 1
+
+// Options control how folders are compared.
+type Options struct {
+	// Include decides which files and folders in the new folder are
+	// compared, so the source can be compared with a set on the same terms it
+	// was backed up. Folders it leaves out aren't descended into. Nil
+	// includes everything.
+	Include func(path string, info os.FileInfo) bool
+
+	// Checksum compares the contents of every file that's in both folders.
+	// Otherwise files with the same size and modification time are assumed
+	// to be the same, like rsync.
+	Checksum bool
+}
+
+// Folders compares two backup sets, or a set and the live source. Files only
+// in newFolder are added, files only in oldFolder are removed, and files in
+// both whose size or MD5 hash differs are changed. The hash is only checked
+// when the modification times differ, unless options ask for checksums.
+// Symlinks are compared by where they point, and anything that has changed
+// type counts as changed.
+func Folders(oldFolder string, newFolder string, options Options) (Changes, error) {
+	changes := Changes{Added: []string{}, Removed: []string{}, Changed: []string{}}
+	oldFiles, err := listFiles(oldFolder, nil)
+	if err != nil {
+		return changes, err
+	}
+	newFiles, err := listFiles(newFolder, options.Include)
+	if err != nil {
+		return changes, err
+	}
+	for path, info := range newFiles {
+		oldInfo, found := oldFiles[path]
+		if !found {
+			changes.Added = append(changes.Added, path)
+			continue
+		}
+		same, err := sameFile(filepath.Join(oldFolder, path), oldInfo, filepath.Join(newFolder, path), info, options.Checksum)
+		if err != nil {
+			return changes, err
+		}
+		if !same {
+			changes.Changed = append(changes.Changed, path)
+		}
+	}
+	for path := range oldFiles {
+		if _, found := newFiles[path]; !found {
+			changes.Removed = append(changes.Removed, path)
+		}
+	}
+	sort.Strings(changes.Added)
+	sort.Strings(changes.Removed)
+	sort.Strings(changes.Changed)
+	return changes, nil
+}
+
+// WriteText writes one change per line prefixed with A, D or M, like git's
+// --name-status.
+func (c Changes) WriteText(w io.Writer) error {
+	for _, group := range []struct {
+		status string
+		paths  []string
+	}{{"A", c.Added}, {"D", c.Removed}, {"M", c.Changed}} {
+		for _, path := range group.paths {
+			if _, err := fmt.Fprintf(w, "%v\t%v\n", group.status, path); err != nil {
+				return err
+			}
+		}
+	}
+	return nil
+}
+
+// listFiles maps the path of every file, symlink and special file under
+// root, relative to root, to its details. Symlinks aren't followed.
+// disk-hog-backup's own metadata files at the root of a set are left out as
+// they aren't backed up data, as is anything include rejects if it's given.
+func listFiles(root string, include func(path string, info os.FileInfo) bool) (map[string]os.FileInfo, error) {
+	files := map[string]os.FileInfo{}
+	err := filepath.Walk(root, func(path string, info os.FileInfo, err error) error {
+		if err != nil {
+			return dherrors.Wrap(dherrors.OpRead, path, err)
+		}
+		if include != nil && path != root && !include(path, info) {
+			if info.IsDir() {
+				return filepath.SkipDir
+			}
+			return nil
+		}
+		if info.IsDir() {
+			return nil
+		}
+		relPath, err := filepath.Rel(root, path)
+		if err != nil {
+			return err
+		}
+		if backup_sets.IsMetadataFile(relPath) {
+			return nil
+		}
+		files[relPath] = info
+		return nil
+	})
+	return files, err
+}
+
+// sameFile compares two files found at the same path. Only regular files are
+// compared by contents, as opening anything else could follow a dangling
+// link or block on a FIFO, and only if their modification times differ
+// unless checksum is set.
+func sameFile(path1 string, info1 os.FileInfo, path2 string, info2 os.FileInfo, checksum bool) (bool, error) {
+	if info1.Mode()&os.ModeType != info2.Mode()&os.ModeType {
+		return false, nil
+	}
+	if info1.Mode()&os.ModeSymlink != 0 {
+		target1, err := os.Readlink(path1)
+		if err != nil {
+			return false, dherrors.Wrap(dherrors.OpRead, path1, err)
+		}
+		target2, err := os.Readlink(path2)
+		if err != nil {
+			return false, dherrors.Wrap(dherrors.OpRead, path2, err)
+		}
+		return target1 == target2, nil
+	}
+	if !info1.Mode().IsRegular() {
+		return true, nil
+	}
+	if info1.Size() != info2.Size() {
+		return false, nil
+	}
+	if !checksum && info1.ModTime().Equal(info2.ModTime()) {
+		return true, nil
+	}
+	return sameContents(path1, path2)
+}
+
+func sameContents(path1 string, path2 string) (bool, error) {
+	hash1, err := dhcopy.HashFile(path1)
+	if err != nil {
+		return false, err
+	}
+	hash2, err := dhcopy.HashFile(path2)
+	if err != nil {
+		return false, err
+	}
+	return bytes.Equal(hash1, hash2), nil
+}
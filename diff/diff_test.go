@@ -0,0 +1,113 @@
+package diff
+
+import (
+	"bytes"
+	"github.com/stretchr/testify/assert"
+	"github.com/timabell/disk-hog-backup/backup_sets"
+	"github.com/timabell/disk-hog-backup/test_helpers"
+	"os"
+	"path/filepath"
+	"testing"
+	"time"
+)
+
+func TestFolders(t *testing.T) {
+	oldSet := test_helpers.CreateTmpFolder("old-set")
+	defer os.RemoveAll(oldSet)
+	newSet := test_helpers.CreateTmpFolder("new-set")
+	defer os.RemoveAll(newSet)
+	test_helpers.WriteFile(filepath.Join(oldSet, "same.txt"), "unchanged")
+	test_helpers.WriteFile(filepath.Join(newSet, "same.txt"), "unchanged")
+	test_helpers.WriteFile(filepath.Join(oldSet, "edited.txt"), "before")
+	test_helpers.WriteFile(filepath.Join(newSet, "edited.txt"), "after!")
+	test_helpers.WriteFile(filepath.Join(oldSet, "deleted.txt"), "gone")
+	test_helpers.WriteFile(filepath.Join(newSet, "sub", "created.txt"), "new")
+	test_helpers.WriteFile(filepath.Join(newSet, backup_sets.ErrorReportName), "read failed on x")
+
+	changes, err := Folders(oldSet, newSet, Options{Checksum: true})
+
+	assert.NoError(t, err)
+	assert.Equal(t, []string{filepath.Join("sub", "created.txt")}, changes.Added)
+	assert.Equal(t, []string{"deleted.txt"}, changes.Removed)
+	assert.Equal(t, []string{"edited.txt"}, changes.Changed, "same size but different contents should count as changed")
+}
+
+func TestFoldersComparesSymlinksWithoutFollowing(t *testing.T) {
+	oldSet := test_helpers.CreateTmpFolder("old-set")
+	defer os.RemoveAll(oldSet)
+	newSet := test_helpers.CreateTmpFolder("new-set")
+	defer os.RemoveAll(newSet)
+	for _, set := range []string{oldSet, newSet} {
+		test_helpers.WriteFile(filepath.Join(set, "folder", "file.txt"), "in a folder")
+		test_helpers.MakeSymlink("missing.txt", filepath.Join(set, "dangling"))
+		test_helpers.MakeSymlink("folder", filepath.Join(set, "to-folder"))
+	}
+	test_helpers.MakeSymlink("folder", filepath.Join(oldSet, "retargeted"))
+	test_helpers.MakeSymlink("elsewhere", filepath.Join(newSet, "retargeted"))
+	test_helpers.WriteFile(filepath.Join(oldSet, "now-a-link"), "was a file")
+	test_helpers.MakeSymlink("folder", filepath.Join(newSet, "now-a-link"))
+
+	changes, err := Folders(oldSet, newSet, Options{})
+
+	assert.NoError(t, err)
+	assert.Empty(t, changes.Added)
+	assert.Empty(t, changes.Removed)
+	assert.Equal(t, []string{"now-a-link", "retargeted"}, changes.Changed)
+}
+
+func TestFoldersOnlyHashesFilesWithDifferentTimes(t *testing.T) {
+	oldSet := test_helpers.CreateTmpFolder("old-set")
+	defer os.RemoveAll(oldSet)
+	newSet := test_helpers.CreateTmpFolder("new-set")
+	defer os.RemoveAll(newSet)
+	test_helpers.WriteFile(filepath.Join(oldSet, "same-time.txt"), "before")
+	test_helpers.WriteFile(filepath.Join(newSet, "same-time.txt"), "after!")
+	test_helpers.WriteFile(filepath.Join(oldSet, "touched.txt"), "unchanged")
+	test_helpers.WriteFile(filepath.Join(newSet, "touched.txt"), "unchanged")
+	test_helpers.WriteFile(filepath.Join(oldSet, "edited.txt"), "before")
+	test_helpers.WriteFile(filepath.Join(newSet, "edited.txt"), "after!")
+	modified := time.Date(2001, 1, 1, 0, 0, 0, 0, time.UTC)
+	test_helpers.SetModified(filepath.Join(oldSet, "same-time.txt"), modified)
+	test_helpers.SetModified(filepath.Join(newSet, "same-time.txt"), modified)
+	test_helpers.SetModified(filepath.Join(oldSet, "touched.txt"), modified)
+	test_helpers.SetModified(filepath.Join(oldSet, "edited.txt"), modified)
+
+	changes, err := Folders(oldSet, newSet, Options{})
+
+	assert.NoError(t, err)
+	assert.Equal(t, []string{"edited.txt"}, changes.Changed, "files with the same size and time should be assumed unchanged")
+
+	changes, err = Folders(oldSet, newSet, Options{Checksum: true})
+
+	assert.NoError(t, err)
+	assert.Equal(t, []string{"edited.txt", "same-time.txt"}, changes.Changed)
+}
+
+func TestFoldersLeavesOutWhatIncludeRejects(t *testing.T) {
+	oldSet := test_helpers.CreateTmpFolder("old-set")
+	defer os.RemoveAll(oldSet)
+	source := test_helpers.CreateTmpFolder("source")
+	defer os.RemoveAll(source)
+	test_helpers.WriteFile(filepath.Join(oldSet, "kept.txt"), "kept")
+	test_helpers.WriteFile(filepath.Join(source, "kept.txt"), "kept")
+	test_helpers.WriteFile(filepath.Join(source, "cache", "big.bin"), "not backed up")
+	test_helpers.WriteFile(filepath.Join(source, "swapfile"), "not backed up")
+	include := func(path string, info os.FileInfo) bool {
+		return info.Name() != "cache" && info.Name() != "swapfile"
+	}
+
+	changes, err := Folders(oldSet, source, Options{Include: include})
+
+	assert.NoError(t, err)
+	assert.Empty(t, changes.Added)
+	assert.Empty(t, changes.Removed)
+}
+
+func TestWriteText(t *testing.T) {
+	changes := Changes{Added: []string{"new.txt"}, Removed: []string{"old.txt"}, Changed: []string{"edited.txt"}}
+	var out bytes.Buffer
+
+	assert.NoError(t, changes.WriteText(&out))
+
+	assert.Equal(t, "A\tnew.txt\nD\told.txt\nM\tedited.txt\n", out.String())
+}
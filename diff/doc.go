@@ -0,0 +1,3 @@
+// Package diff compares two folders, such as two backup sets or a set and
+// the live source.
+package diff
@@ -0,0 +1,45 @@
+package main
+
+import (
+	"encoding/json"
+	"flag"
+	"fmt"
+	"github.com/timabell/disk-hog-backup/diff"
+	"log"
+	"os"
+)
+
+func runDiff(args []string) {
+	flags := flag.NewFlagSet("diff", flag.ExitOnError)
+	asJSON := flags.Bool("json", false, "output the changes as JSON")
+	checksum := flags.Bool("checksum", false, "compare the contents of every file, instead of assuming files with the same size and modification time are unchanged")
+	flags.Usage = func() {
+		fmt.Fprintln(flags.Output(), "usage: disk-hog-backup diff [-json] [-checksum] <old set folder> <new set or source folder>")
+		flags.PrintDefaults()
+	}
+	flags.Parse(args)
+	if flags.NArg() != 2 {
+		flags.Usage()
+		os.Exit(2)
+	}
+	changes, err := diff.Folders(flags.Arg(0), flags.Arg(1), diff.Options{Checksum: *checksum})
+	if err != nil {
+		log.Fatalf("Diff failed: %s", err)
+	}
+	writeChanges(changes, *asJSON)
+}
+
+func writeChanges(changes diff.Changes, asJSON bool) {
+	var err error
+	if asJSON {
+		err = json.NewEncoder(os.Stdout).Encode(struct {
+			SchemaVersion int `json:"schema_version"`
+			diff.Changes
+		}{diff.SchemaVersion, changes})
+	} else {
+		err = changes.WriteText(os.Stdout)
+	}
+	if err != nil {
+		log.Fatal(err)
+	}
+}
@@ -1,21 +1,291 @@
+// Command disk-hog-backup backs up a folder into a new dated set in a
+// destination folder. Run with -h for the options. Subcommands list, diff,
+// compare, latest-status, find, history, dedupe, gc and restore work with
+// existing sets, and install-service schedules backups.
 package main
 
 import (
+	"errors"
 	"flag"
-	backup_sets2 "github.com/timabell/disk-hog-backup/backup"
+	"fmt"
+	"github.com/timabell/disk-hog-backup/backup"
+	"github.com/timabell/disk-hog-backup/backup_sets"
+	"github.com/timabell/disk-hog-backup/dhcopy"
+	"github.com/timabell/disk-hog-backup/dherrors"
+	"github.com/timabell/disk-hog-backup/notify"
+	"github.com/timabell/disk-hog-backup/priority"
 	"log"
+	"os"
+	"time"
+)
+
+// Exit codes of a backup run, documented in the README. Keep them stable, as
+// cron wrappers and monitoring depend on them.
+const (
+	// everything was backed up
+	exitSuccess = 0
+
+	// the backup failed or the options were invalid
+	exitFailed = 1
+
+	// the backup completed but some files couldn't be copied
+	exitPartialSuccess = 2
+
+	// the destination is mounted read-only, or switched to read-only mid-run
+	exitReadOnlyDestination = 3
+
+	// everything was copied but the set couldn't be marked complete
+	exitFinalizeFailed = 4
 )
 
 var source string
 var destination string
+var sourceList string
+var filesFrom string
+var sourceListNul bool
+var bandwidthLimit float64
+var nice int
+var ioPriority string
+var structureOnly bool
+var noDefaultSkips bool
+var notifyEmail string
+var notifyFrom string
+var smtpServer string
+var pingURL string
+var webhookURL string
+var notifyDesktop bool
+var keepGoing bool
+var maxDepth int
+var includeSetMetadata bool
+var dedupe bool
+var followSymlinks bool
+var relativeSymlinks bool
+var oneFileSystem bool
+var minSize sizeFlag
+var maxSize sizeFlag
+var changedSince timeFlag
+var filters filterFlag
+var removeIncomplete bool
+var chunkSize chunkSizeFlag
+var volatile string
+var folderBreakdown bool
+var largestFiles int
+var recordSpecialFiles bool
+var verifyWrites bool
+var retries int
+var retryDelay time.Duration
 
 func main() {
+	if len(os.Args) > 1 {
+		switch os.Args[1] {
+		case "list":
+			runList(os.Args[2:])
+			return
+		case "diff":
+			runDiff(os.Args[2:])
+			return
+		case "latest-status":
+			runLatestStatus(os.Args[2:])
+			return
+		case "dedupe":
+			runDedupe(os.Args[2:])
+			return
+		case "find":
+			runFind(os.Args[2:])
+			return
+		case "gc":
+			runGC(os.Args[2:])
+			return
+		case "compare":
+			runCompare(os.Args[2:])
+			return
+		case "history":
+			runHistory(os.Args[2:])
+			return
+		case "install-service":
+			runInstallService(os.Args[2:])
+			return
+		case "restore":
+			runRestore(os.Args[2:])
+			return
+		}
+	}
+	runBackup()
+}
+
+func runBackup() {
 	flag.StringVar(&source, "source", "", "source folder to back up")
 	flag.StringVar(&destination, "destination", "", "destination folder for backups")
-	flag.Parse()
-	_, err := backup_sets2.Backup(source, destination)
-	if err != nil{
-		log.Fatalf("Backup failed: %s", err)
+	flag.StringVar(&sourceList, "source-list", "", "file listing paths within source to back up with everything in them, one per line, instead of the whole source folder, - for standard input")
+	flag.StringVar(&filesFrom, "files-from", "", "file listing the exact paths within source to back up, one per line, without copying what's in listed folders unless that's listed too, - for standard input")
+	flag.BoolVar(&sourceListNul, "0", false, "paths in the -source-list or -files-from list are separated by NUL characters, as from find -print0")
+	flag.Float64Var(&bandwidthLimit, "bwlimit", 0, "limit reading from the source to this many MB/s, 0 for unlimited")
+	flag.IntVar(&nice, "nice", 0, "run with this CPU niceness, from -20 to 19 (the nearest priority class on Windows)")
+	flag.StringVar(&ioPriority, "ionice", "", "run with this I/O priority, idle or a best-effort level 0-7 (levels are Linux only, idle is background mode on macOS and Windows)")
+	flag.BoolVar(&structureOnly, "structure-only", false, "recreate the folder tree without copying any files")
+	flag.BoolVar(&noDefaultSkips, "no-default-skips", false, "include swap files, core dumps, trash and lost+found, which are skipped by default")
+	flag.StringVar(&notifyEmail, "notify-email", "", "email address to send the result of the backup to")
+	flag.StringVar(&notifyFrom, "notify-from", "disk-hog-backup@localhost", "sender address for notification emails")
+	flag.StringVar(&smtpServer, "smtp-server", "localhost:25", "SMTP server host:port for notification emails, credentials are read from DHB_SMTP_USER and DHB_SMTP_PASSWORD")
+	flag.StringVar(&pingURL, "ping-url", "", "healthchecks.io style URL to ping when the backup starts, succeeds (URL) or fails (URL/fail)")
+	flag.StringVar(&webhookURL, "webhook", "", "URL to POST JSON events to when the backup starts and finishes")
+	flag.BoolVar(&notifyDesktop, "notify-desktop", false, "show a desktop notification when the backup finishes (notify-send on Linux, osascript on macOS, a PowerShell toast on Windows)")
+	flag.BoolVar(&keepGoing, "keep-going", false, "carry on past files that can't be backed up, listing them in "+backup_sets.ErrorReportName+" in the set")
+	flag.IntVar(&maxDepth, "max-depth", 0, "only copy this many levels of folders below the source, 0 for no limit")
+	flag.BoolVar(&includeSetMetadata, "include-set-metadata", false, "when the source contains old backup sets, copy their "+backup_sets.ErrorReportName+" etc. as if they were data")
+	flag.BoolVar(&dedupe, "dedupe", false, "hardlink files with identical contents within the set so duplicates only take space once")
+	flag.BoolVar(&followSymlinks, "follow-symlinks", false, "copy what symlinks point to instead of recreating the links")
+	flag.BoolVar(&relativeSymlinks, "relative-symlinks", false, "rewrite absolute symlinks that point inside the source as relative links so the set is self-contained")
+	flag.BoolVar(&oneFileSystem, "one-file-system", false, "don't descend into folders on other filesystems, like mounted drives")
+	flag.Var(&minSize, "min-size", "skip files smaller than this, e.g. 1K")
+	flag.Var(&maxSize, "max-size", "skip files bigger than this, e.g. 2G")
+	flag.Var(&filters, "filter", "rsync-style rule, '+ pattern' to include or '- pattern' to exclude, can be repeated and the first matching rule wins, e.g. -filter '+ /photos/***' -filter '- *'")
+	flag.Var(&changedSince, "changed-since", "only copy files modified after this date/time (e.g. 2024-06-01) or duration ago (e.g. 36h, 7d), the set is marked partial so it isn't used as the latest full backup")
+	flag.BoolVar(&removeIncomplete, "gc", false, "delete sets left incomplete by crashed or killed runs before starting, sets that may still be being written by another run are left alone")
+	flag.Var(&chunkSize, "chunk-size", "read and write file contents this much at a time, from 4K to 64M, default 32K")
+	flag.StringVar(&volatile, "volatile", "warn", "what to do with files that are probably in use, like browser database journals and lock files: warn (copy them with a warning), skip or copy")
+	flag.BoolVar(&folderBreakdown, "folder-breakdown", false, "show how much was copied from each top level folder of the source in the summary")
+	flag.IntVar(&largestFiles, "largest-files", 0, "list this many of the biggest files copied in the summary")
+	flag.BoolVar(&recordSpecialFiles, "record-special-files", false, "list FIFOs, sockets and device nodes, which can't be copied, in "+backup_sets.SpecialFilesName+" in the set so they can be recreated")
+	flag.BoolVar(&verifyWrites, "verify-writes", false, "read back each file after copying it and check it matches what was written")
+	flag.IntVar(&retries, "retries", 0, "try copying a file this many more times after transient I/O errors, like a network filesystem timing out")
+	flag.DurationVar(&retryDelay, "retry-delay", time.Second, "wait this long before the first retry, doubling for each retry after")
+	flag.CommandLine.Init(os.Args[0], flag.ContinueOnError)
+	if err := flag.CommandLine.Parse(os.Args[1:]); err != nil {
+		if err == flag.ErrHelp {
+			os.Exit(exitSuccess)
+		}
+		os.Exit(exitFailed) // not 2 as flag would, that means partial success
+	}
+	options, err := prepareBackup()
+	if err != nil {
+		notifyFinish(notify.Result{Source: source, Destination: destination, Err: err})
+		log.Printf("Backup failed: %s", err)
+		os.Exit(exitFailed)
+	}
+	notifyStart(notify.Result{Source: source, Destination: destination})
+	setName, stats, err := backup.Backup(source, destination, options)
+	notifyFinish(notify.Result{Source: source, Destination: destination, SetName: setName, Stats: stats, Err: err})
+	if dherrors.IsReadOnly(err) {
+		log.Printf("Backup failed: the destination is read-only: %s", err)
+		log.Printf("Check %v is mounted read-write. If it was writable when the backup started, the drive has probably been remounted read-only after filesystem errors; check the kernel log (dmesg) and run fsck before trying again.", destination)
+		if setName != "" {
+			log.Printf("Set %v is incomplete.", setName)
+		}
+		os.Exit(exitReadOnlyDestination)
+	}
+	if backup.IsFinalizeError(err) {
+		log.Printf("Backup failed: %s", err)
+		log.Printf("The files were copied but set %v is still marked incomplete. Check there is free space on %v; the set can be checked with the diff command against the source.", setName, destination)
+		os.Exit(exitFinalizeFailed)
+	}
+	if err != nil {
+		log.Printf("Backup failed: %s", err)
+		os.Exit(exitFailed)
+	}
+	if len(stats.Errors) > 0 {
+		log.Printf("Backup finished with %v errors, see %v in the set", len(stats.Errors), backup_sets.ErrorReportName)
+		os.Exit(exitPartialSuccess)
+	}
+}
+
+// prepareBackup lowers the priority of the process if asked to and turns the
+// flags into backup options. Errors are returned rather than exiting so the
+// failure is still notified.
+func prepareBackup() (backup.Options, error) {
+	options := backup.Options{RemoveIncomplete: removeIncomplete}
+	bandwidthBytes, err := bandwidthLimitBytes(bandwidthLimit)
+	if err != nil {
+		return options, err
+	}
+	if nice != 0 {
+		if err := priority.SetNice(nice); err != nil {
+			return options, fmt.Errorf("couldn't set niceness: %w", err)
+		}
+	}
+	if ioPriority != "" {
+		if err := priority.SetIOPriority(ioPriority); err != nil {
+			return options, fmt.Errorf("couldn't set I/O priority: %w", err)
+		}
+	}
+	options.Copy.BandwidthLimit = bandwidthBytes
+	options.Copy.StructureOnly = structureOnly
+	options.Copy.NoDefaultSkips = noDefaultSkips
+	options.Copy.KeepGoing = keepGoing
+	options.Copy.MaxDepth = maxDepth
+	options.Copy.IncludeSetMetadata = includeSetMetadata
+	options.Copy.Dedupe = dedupe
+	options.Copy.FollowSymlinks = followSymlinks
+	options.Copy.RelativeSymlinks = relativeSymlinks
+	options.Copy.OneFileSystem = oneFileSystem
+	options.Copy.MinSize = int64(minSize)
+	options.Copy.MaxSize = int64(maxSize)
+	options.Copy.ChangedSince = changedSince.Time
+	options.Copy.Filters = filters
+	options.Copy.ChunkSize = int(chunkSize.sizeFlag)
+	options.Copy.FolderBreakdown = folderBreakdown
+	options.Copy.LargestFiles = largestFiles
+	options.Copy.RecordSpecialFiles = recordSpecialFiles
+	options.Copy.VerifyWrites = verifyWrites
+	options.Copy.Retries = retries
+	options.Copy.RetryDelay = retryDelay
+	volatilePolicy, err := dhcopy.ParseVolatilePolicy(volatile)
+	if err != nil {
+		return options, err
+	}
+	options.Copy.Volatile = volatilePolicy
+	if sourceList != "" && filesFrom != "" {
+		return options, errors.New("-source-list and -files-from can't be used together")
+	}
+	listPath := sourceList
+	if filesFrom != "" {
+		listPath = filesFrom
+		options.Copy.ExactPaths = true
+	}
+	if listPath != "" {
+		paths, err := backup.ReadSourceList(listPath, sourceListNul)
+		if err != nil {
+			return options, fmt.Errorf("couldn't read source list: %w", err)
+		}
+		options.SourceList = paths
+		options.SourceListGiven = true
+	}
+	return options, nil
+}
+
+func notifyStart(result notify.Result) {
+	if pingURL != "" {
+		if err := (notify.Ping{URL: pingURL}).Start(); err != nil {
+			log.Printf("Couldn't ping %v: %s", pingURL, err)
+		}
+	}
+	if webhookURL != "" {
+		if err := (notify.Webhook{URL: webhookURL}).Start(result); err != nil {
+			log.Printf("Couldn't call webhook %v: %s", webhookURL, err)
+		}
 	}
 }
 
+func notifyFinish(result notify.Result) {
+	if pingURL != "" {
+		if err := (notify.Ping{URL: pingURL}).Finish(result); err != nil {
+			log.Printf("Couldn't ping %v: %s", pingURL, err)
+		}
+	}
+	if webhookURL != "" {
+		if err := (notify.Webhook{URL: webhookURL}).Finish(result); err != nil {
+			log.Printf("Couldn't call webhook %v: %s", webhookURL, err)
+		}
+	}
+	if notifyEmail != "" {
+		email := notify.Email{To: notifyEmail, From: notifyFrom, Server: smtpServer}
+		if err := email.Send(result.Subject(), result.Body()); err != nil {
+			log.Printf("Couldn't send notification email: %s", err)
+		}
+	}
+	if notifyDesktop {
+		if err := (notify.Desktop{}).Finish(result); err != nil {
+			log.Printf("Couldn't show desktop notification: %s", err)
+		}
+	}
+}
@@ -0,0 +1,36 @@
+package main
+
+import (
+	"flag"
+	"fmt"
+	"github.com/timabell/disk-hog-backup/dhcopy"
+	"github.com/timabell/disk-hog-backup/search"
+	"log"
+	"os"
+)
+
+// runFind lists files matching a pattern in every backup set.
+func runFind(args []string) {
+	flags := flag.NewFlagSet("find", flag.ExitOnError)
+	dest := flags.String("destination", "", "destination folder containing backup sets")
+	flags.Usage = func() {
+		fmt.Fprintln(flags.Output(), "usage: disk-hog-backup find -destination <folder> <pattern>")
+		fmt.Fprintln(flags.Output(), "patterns are globs like 'docs/**/*.odt', or just a file name glob to match in any folder")
+		flags.PrintDefaults()
+	}
+	flags.Parse(args)
+	if *dest == "" || flags.NArg() != 1 {
+		flags.Usage()
+		os.Exit(2)
+	}
+	found, err := search.Find(*dest, flags.Arg(0))
+	if err != nil {
+		log.Fatalf("Find failed: %s", err)
+	}
+	for _, file := range found {
+		fmt.Printf("%v\t%v\t%x\t%v\n", file.Set, dhcopy.FormatBytes(file.Size), file.Hash, file.Path)
+	}
+	if len(found) == 0 {
+		os.Exit(1)
+	}
+}
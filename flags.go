@@ -0,0 +1,125 @@
+package main
+
+import (
+	"fmt"
+	"github.com/timabell/disk-hog-backup/dhcopy"
+	"math"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// sizeFlag is a command line flag for a size like 500M, see dhcopy.ParseSize.
+type sizeFlag int64
+
+func (s *sizeFlag) String() string {
+	return dhcopy.FormatBytes(int64(*s))
+}
+
+func (s *sizeFlag) Set(value string) error {
+	size, err := dhcopy.ParseSize(value)
+	if err != nil {
+		return err
+	}
+	*s = sizeFlag(size)
+	return nil
+}
+
+// chunkSizeFlag is a sizeFlag for -chunk-size, which is a buffer held in
+// memory so has to be within sensible limits.
+type chunkSizeFlag struct {
+	sizeFlag
+}
+
+const (
+	minChunkSize = 4 * 1024
+	maxChunkSize = 64 * 1024 * 1024
+)
+
+func (c *chunkSizeFlag) Set(value string) error {
+	var size sizeFlag
+	if err := size.Set(value); err != nil {
+		return err
+	}
+	if size < minChunkSize || size > maxChunkSize {
+		return fmt.Errorf("chunk size %v is out of range, it must be between %v and %v", value, dhcopy.FormatBytes(minChunkSize), dhcopy.FormatBytes(maxChunkSize))
+	}
+	c.sizeFlag = size
+	return nil
+}
+
+// bandwidthLimitBytes converts -bwlimit from MB/s to bytes per second. Only
+// 0 means unlimited, so anything else that comes to less than a byte is
+// refused rather than silently lifting the limit.
+func bandwidthLimitBytes(mbPerSecond float64) (int64, error) {
+	if mbPerSecond == 0 {
+		return 0, nil
+	}
+	bytes := mbPerSecond * 1024 * 1024
+	if !(bytes >= 1 && bytes < math.MaxInt64) {
+		return 0, fmt.Errorf("invalid -bwlimit %v, expected a number of MB/s or 0 for unlimited", mbPerSecond)
+	}
+	return int64(bytes), nil
+}
+
+// timeFlag is a command line flag for a point in time, see parseTime.
+type timeFlag struct {
+	time.Time
+}
+
+func (t *timeFlag) String() string {
+	if t.IsZero() {
+		return ""
+	}
+	return t.Format(time.RFC3339)
+}
+
+func (t *timeFlag) Set(value string) error {
+	parsed, err := parseTime(value, time.Now())
+	if err != nil {
+		return err
+	}
+	t.Time = parsed
+	return nil
+}
+
+var timeFormats = []string{time.RFC3339, "2006-01-02T15:04:05", "2006-01-02 15:04:05", "2006-01-02"}
+
+// parseTime parses a local date/time like 2024-06-01 or 2024-06-01T09:30:00,
+// or a duration before now like 36h or 7d.
+func parseTime(value string, now time.Time) (time.Time, error) {
+	for _, format := range timeFormats {
+		if parsed, err := time.ParseInLocation(format, value, time.Local); err == nil {
+			return parsed, nil
+		}
+	}
+	if strings.HasSuffix(value, "d") {
+		if days, err := strconv.Atoi(strings.TrimSuffix(value, "d")); err == nil {
+			return now.AddDate(0, 0, -days), nil
+		}
+	}
+	if duration, err := time.ParseDuration(value); err == nil {
+		return now.Add(-duration), nil
+	}
+	return time.Time{}, fmt.Errorf("invalid time %q, expected e.g. 2024-06-01, 2024-06-01T09:30:00, 36h or 7d", value)
+}
+
+// filterFlag collects -filter rules in the order they're given.
+type filterFlag []dhcopy.FilterRule
+
+func (f *filterFlag) String() string {
+	rules := make([]string, len(*f))
+	for i, rule := range *f {
+		rules[i] = rule.String()
+	}
+	return strings.Join(rules, ", ")
+}
+
+func (f *filterFlag) Set(value string) error {
+	rule, err := dhcopy.ParseFilterRule(value)
+	if err != nil {
+		return err
+	}
+	*f = append(*f, rule)
+	return nil
+}
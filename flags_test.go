@@ -0,0 +1,47 @@
+package main
+
+import (
+	"github.com/stretchr/testify/assert"
+	"testing"
+	"time"
+)
+
+func TestParseTime(t *testing.T) {
+	now := time.Date(2024, 6, 10, 12, 0, 0, 0, time.Local)
+	for value, expected := range map[string]time.Time{
+		"2024-06-01":          time.Date(2024, 6, 1, 0, 0, 0, 0, time.Local),
+		"2024-06-01T09:30:00": time.Date(2024, 6, 1, 9, 30, 0, 0, time.Local),
+		"36h":                 now.Add(-36 * time.Hour),
+		"7d":                  time.Date(2024, 6, 3, 12, 0, 0, 0, time.Local),
+	} {
+		parsed, err := parseTime(value, now)
+		assert.NoError(t, err, value)
+		assert.True(t, expected.Equal(parsed), "%v parsed as %v", value, parsed)
+	}
+}
+
+func TestParseTimeRejectsNonsense(t *testing.T) {
+	_, err := parseTime("last tuesday", time.Now())
+	assert.Error(t, err)
+}
+
+func TestChunkSizeLimits(t *testing.T) {
+	var size chunkSizeFlag
+	assert.NoError(t, size.Set("1M"))
+	assert.Equal(t, sizeFlag(1024*1024), size.sizeFlag)
+	for _, value := range []string{"0", "1K", "100G"} {
+		assert.Error(t, size.Set(value), value)
+	}
+}
+
+func TestBandwidthLimitBytes(t *testing.T) {
+	for mbPerSecond, expected := range map[float64]int64{0: 0, 1: 1024 * 1024, 0.5: 512 * 1024} {
+		bytes, err := bandwidthLimitBytes(mbPerSecond)
+		assert.NoError(t, err)
+		assert.Equal(t, expected, bytes)
+	}
+	for _, mbPerSecond := range []float64{-1, 0.0000001} {
+		_, err := bandwidthLimitBytes(mbPerSecond)
+		assert.Error(t, err, "%v shouldn't mean unlimited", mbPerSecond)
+	}
+}
@@ -0,0 +1,79 @@
+package main
+
+import (
+	"bufio"
+	"flag"
+	"fmt"
+	"github.com/timabell/disk-hog-backup/backup_sets"
+	"log"
+	"os"
+	"path/filepath"
+	"strings"
+	"time"
+)
+
+// runGC finds sets left incomplete by crashed or killed backups and deletes
+// them, asking first unless -force is given. Sets whose backup may still be
+// running are left alone unless -include-running is given.
+func runGC(args []string) {
+	flags := flag.NewFlagSet("gc", flag.ExitOnError)
+	dest := flags.String("destination", "", "destination folder containing backup sets")
+	force := flags.Bool("force", false, "delete without asking for confirmation")
+	includeRunning := flags.Bool("include-running", false, "also delete sets whose backup may still be running, because its process is alive or it started within the last day on another machine")
+	flags.Parse(args)
+	if *dest == "" {
+		flags.Usage()
+		os.Exit(2)
+	}
+	incomplete, err := backup_sets.ListIncompleteSets(*dest)
+	if err != nil {
+		log.Fatalf("Listing sets failed: %s", err)
+	}
+	var abandoned []string
+	for _, setName := range incomplete {
+		setFolder := filepath.Join(*dest, setName)
+		running, err := backup_sets.MayBeRunning(setFolder, time.Now())
+		if err != nil {
+			log.Fatal(err)
+		}
+		if running && !*includeRunning {
+			fmt.Printf("skipping %v, %v\n", setName, describeRun(setFolder))
+			continue
+		}
+		abandoned = append(abandoned, setName)
+	}
+	if len(abandoned) == 0 {
+		fmt.Println("no incomplete sets to delete")
+		return
+	}
+	fmt.Println("incomplete sets:")
+	for _, setName := range abandoned {
+		fmt.Printf("  %v\n", setName)
+	}
+	if !*force && !confirm(fmt.Sprintf("delete %v incomplete sets?", len(abandoned))) {
+		return
+	}
+	for _, setName := range abandoned {
+		if err := backup_sets.DeleteSet(*dest, setName); err != nil {
+			log.Fatalf("Deleting set failed: %s", err)
+		}
+		fmt.Printf("deleted %v\n", setName)
+	}
+}
+
+// describeRun says which backup may still be writing into a set.
+func describeRun(setFolder string) string {
+	run, found, err := backup_sets.ReadRunInfo(setFolder)
+	if err != nil || !found {
+		return "it was started recently and may still be running"
+	}
+	return fmt.Sprintf("it may still be being written by process %v on %v, started %v", run.PID, run.Host, run.Started.Format("2006-01-02 15:04:05"))
+}
+
+// confirm asks a yes/no question on the terminal, defaulting to no.
+func confirm(question string) bool {
+	fmt.Printf("%v [y/N] ", question)
+	answer, _ := bufio.NewReader(os.Stdin).ReadString('\n')
+	answer = strings.ToLower(strings.TrimSpace(answer))
+	return answer == "y" || answer == "yes"
+}
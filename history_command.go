@@ -0,0 +1,40 @@
+package main
+
+import (
+	"flag"
+	"fmt"
+	"github.com/timabell/disk-hog-backup/dhcopy"
+	"github.com/timabell/disk-hog-backup/search"
+	"log"
+	"os"
+	"path/filepath"
+)
+
+// runHistory shows how a file changed across the backup sets.
+func runHistory(args []string) {
+	flags := flag.NewFlagSet("history", flag.ExitOnError)
+	dest := flags.String("destination", "", "destination folder containing backup sets")
+	flags.Usage = func() {
+		fmt.Fprintln(flags.Output(), "usage: disk-hog-backup history -destination <folder> <path within the sets>")
+		flags.PrintDefaults()
+	}
+	flags.Parse(args)
+	if *dest == "" || flags.NArg() != 1 {
+		flags.Usage()
+		os.Exit(2)
+	}
+	versions, err := search.History(*dest, filepath.ToSlash(flags.Arg(0)))
+	if err != nil {
+		log.Fatalf("History failed: %s", err)
+	}
+	if len(versions) == 0 {
+		log.Fatalf("%v isn't in any set", flags.Arg(0))
+	}
+	for _, version := range versions {
+		if version.Status == "missing" {
+			fmt.Printf("%v\t%v\n", version.Set, version.Status)
+			continue
+		}
+		fmt.Printf("%v\t%v\t%v\t%x\n", version.Set, version.Status, dhcopy.FormatBytes(version.Size), version.Hash)
+	}
+}
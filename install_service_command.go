@@ -0,0 +1,105 @@
+package main
+
+import (
+	"flag"
+	"fmt"
+	"github.com/timabell/disk-hog-backup/service"
+	"io/ioutil"
+	"log"
+	"os"
+	"path/filepath"
+	"runtime"
+	"strings"
+)
+
+// runInstallService writes a systemd user service and timer that run a
+// backup with the given options on a schedule.
+func runInstallService(args []string) {
+	flags := flag.NewFlagSet("install-service", flag.ExitOnError)
+	schedule := flags.String("schedule", "daily", "hourly, daily, weekly or a systemd calendar expression like 'Mon..Fri 18:00'")
+	name := flags.String("name", "disk-hog-backup", "name of the systemd units, to allow more than one backup job")
+	flags.Usage = func() {
+		fmt.Fprintln(flags.Output(), "usage: disk-hog-backup install-service [-schedule daily] [-name disk-hog-backup] -- <backup options>")
+		flags.PrintDefaults()
+	}
+	flags.Parse(args)
+	if flags.NArg() == 0 {
+		flags.Usage()
+		os.Exit(2)
+	}
+	if runtime.GOOS != "linux" {
+		log.Fatal("install-service only supports systemd on Linux")
+	}
+	executable, err := os.Executable()
+	if err != nil {
+		log.Fatalf("Couldn't find the disk-hog-backup executable: %s", err)
+	}
+	configDir, err := os.UserConfigDir()
+	if err != nil {
+		log.Fatal(err)
+	}
+	unitDir := filepath.Join(configDir, "systemd", "user")
+	if err := os.MkdirAll(unitDir, os.ModePerm); err != nil {
+		log.Fatal(err)
+	}
+	backupArgs, err := absolutePathArgs(flags.Args())
+	if err != nil {
+		log.Fatal(err)
+	}
+	serviceUnit, timerUnit := service.SystemdUnits(*name, append([]string{executable}, backupArgs...), *schedule)
+	for file, contents := range map[string]string{*name + ".service": serviceUnit, *name + ".timer": timerUnit} {
+		path := filepath.Join(unitDir, file)
+		if err := ioutil.WriteFile(path, []byte(contents), 0644); err != nil {
+			log.Fatal(err)
+		}
+		fmt.Printf("wrote %v\n", path)
+	}
+	fmt.Printf("to start the schedule run:\n  systemctl --user daemon-reload\n  systemctl --user enable --now %v.timer\n", *name)
+	fmt.Println("and to keep it running while you're logged out:\n  loginctl enable-linger")
+}
+
+// pathFlags are the backup options that take a path, - meaning standard
+// input where it's allowed.
+var pathFlags = map[string]bool{"source": true, "destination": true, "source-list": true, "files-from": true}
+
+// absolutePathArgs makes the paths in backup options absolute, as systemd
+// runs the service from a different working directory.
+func absolutePathArgs(args []string) ([]string, error) {
+	result := append([]string{}, args...)
+	for i := 0; i < len(result); i++ {
+		arg := result[i]
+		if arg == "--" {
+			break // the rest aren't flags
+		}
+		if !strings.HasPrefix(arg, "-") {
+			continue // the value of some other flag
+		}
+		name := strings.TrimLeft(arg, "-")
+		if equals := strings.IndexByte(name, '='); equals >= 0 {
+			if pathFlags[name[:equals]] {
+				path, err := absolutePath(name[equals+1:])
+				if err != nil {
+					return nil, err
+				}
+				result[i] = "-" + name[:equals] + "=" + path
+			}
+			continue
+		}
+		if pathFlags[name] && i+1 < len(result) {
+			path, err := absolutePath(result[i+1])
+			if err != nil {
+				return nil, err
+			}
+			result[i+1] = path
+			i++
+		}
+	}
+	return result, nil
+}
+
+func absolutePath(path string) (string, error) {
+	if path == "-" || path == "" {
+		return path, nil
+	}
+	return filepath.Abs(path)
+}
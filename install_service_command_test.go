@@ -0,0 +1,25 @@
+package main
+
+import (
+	"github.com/stretchr/testify/assert"
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestAbsolutePathArgs(t *testing.T) {
+	workingDir, err := os.Getwd()
+	assert.NoError(t, err)
+
+	args, err := absolutePathArgs([]string{"-volatile", "skip", "-source", "docs", "--destination=../backups", "-files-from", "-", "-keep-going", "-filter", "- *.tmp"})
+
+	assert.NoError(t, err)
+	assert.Equal(t, []string{
+		"-volatile", "skip",
+		"-source", filepath.Join(workingDir, "docs"),
+		"-destination=" + filepath.Join(filepath.Dir(workingDir), "backups"),
+		"-files-from", "-",
+		"-keep-going",
+		"-filter", "- *.tmp",
+	}, args)
+}
@@ -0,0 +1,55 @@
+package main
+
+import (
+	"encoding/json"
+	"flag"
+	"fmt"
+	"github.com/timabell/disk-hog-backup/status"
+	"os"
+	"time"
+)
+
+// runLatestStatus reports on the most recent set in the style of a Nagios
+// plugin: one line of output and an exit code of 0 (OK), 1 (WARNING),
+// 2 (CRITICAL) or 3 (UNKNOWN).
+func runLatestStatus(args []string) {
+	flags := flag.NewFlagSet("latest-status", flag.ContinueOnError)
+	dest := flags.String("destination", "", "destination folder containing backup sets")
+	warnAge := flags.Duration("warn-age", 0, "warn if the latest set is older than this, e.g. 36h")
+	critAge := flags.Duration("crit-age", 0, "critical if the latest set is older than this, e.g. 72h")
+	uniqueBytes := flags.Bool("unique-bytes", false, "also work out how much space deleting the set would free, which reads through the whole set")
+	asJSON := flags.Bool("json", false, "output the status as JSON")
+	if err := flags.Parse(args); err != nil {
+		os.Exit(int(status.Unknown)) // not 2, that means critical
+	}
+	if *dest == "" {
+		fmt.Println("UNKNOWN - no -destination given")
+		os.Exit(int(status.Unknown))
+	}
+	latest, err := status.Latest(*dest, time.Now())
+	if err != nil {
+		fmt.Printf("%v - %s\n", status.Critical, err)
+		os.Exit(int(status.Critical))
+	}
+	if *uniqueBytes && latest.Complete {
+		if err := latest.AddUniqueBytes(*dest); err != nil {
+			fmt.Printf("%v - %s\n", status.Unknown, err)
+			os.Exit(int(status.Unknown))
+		}
+	}
+	level := latest.Check(*warnAge, *critAge)
+	if *asJSON {
+		err = json.NewEncoder(os.Stdout).Encode(struct {
+			SchemaVersion int `json:"schema_version"`
+			status.Status
+			Level string `json:"level"`
+		}{status.SchemaVersion, latest, level.String()})
+		if err != nil {
+			fmt.Printf("%v - %s\n", status.Unknown, err)
+			os.Exit(int(status.Unknown))
+		}
+	} else {
+		fmt.Printf("%v - %v\n", level, latest)
+	}
+	os.Exit(int(level))
+}
@@ -0,0 +1,65 @@
+package main
+
+import (
+	"flag"
+	"fmt"
+	"github.com/timabell/disk-hog-backup/backup_sets"
+	"github.com/timabell/disk-hog-backup/dhcopy"
+	"github.com/timabell/disk-hog-backup/status"
+	"log"
+	"os"
+	"path/filepath"
+)
+
+// runList lists the sets in a destination, oldest first, with whether each one
+// is a complete full backup and its size.
+func runList(args []string) {
+	flags := flag.NewFlagSet("list", flag.ExitOnError)
+	dest := flags.String("destination", "", "destination folder containing backup sets")
+	uniqueBytes := flags.Bool("unique-bytes", false, "also show how much space deleting each set would free, which reads through every set")
+	flags.Parse(args)
+	if *dest == "" {
+		flags.Usage()
+		os.Exit(2)
+	}
+	sets, err := backup_sets.ListAllSets(*dest)
+	if err != nil {
+		log.Fatalf("Listing sets failed: %s", err)
+	}
+	for _, setName := range sets {
+		setFolder := filepath.Join(*dest, setName)
+		state, err := setState(setFolder)
+		if err != nil {
+			log.Fatal(err)
+		}
+		size, err := status.SetSize(setFolder)
+		if err != nil {
+			log.Fatal(err)
+		}
+		line := fmt.Sprintf("%v\t%v\t%v", setName, state, dhcopy.FormatBytes(size))
+		if *uniqueBytes {
+			unique, err := status.UniqueBytes(setFolder)
+			if err != nil {
+				log.Fatal(err)
+			}
+			line += fmt.Sprintf(", %v unique", dhcopy.FormatBytes(unique))
+		}
+		fmt.Println(line)
+	}
+}
+
+// setState describes a set as complete, partial (with why) or incomplete.
+func setState(setFolder string) (string, error) {
+	complete, err := backup_sets.IsComplete(setFolder)
+	if err != nil || !complete {
+		return "incomplete", err
+	}
+	info, _, err := backup_sets.ReadSetInfo(setFolder)
+	if err != nil {
+		return "", err
+	}
+	if info.Partial != "" {
+		return "partial, " + info.Partial, nil
+	}
+	return "complete", nil
+}
@@ -0,0 +1,70 @@
+package notify
+
+import (
+	"fmt"
+	"os/exec"
+	"runtime"
+	"strings"
+)
+
+// Desktop pops up a notification on the desktop of whoever is running the
+// backup, using notify-send on Linux, osascript on macOS and a PowerShell
+// toast on Windows.
+type Desktop struct{}
+
+// Finish shows how the backup went: the error if it failed, and the summary.
+func (d Desktop) Finish(result Result) error {
+	lines := result.summary()
+	if result.Err != nil {
+		lines = append([]string{result.Err.Error()}, lines...)
+	}
+	return d.Send(result.Subject(), strings.Join(lines, "\n"))
+}
+
+func (d Desktop) Send(subject string, body string) error {
+	command, err := desktopCommand(runtime.GOOS, subject, body)
+	if err != nil {
+		return err
+	}
+	output, err := exec.Command(command[0], command[1:]...).CombinedOutput()
+	if err != nil {
+		return fmt.Errorf("%v failed: %s: %s", command[0], err, strings.TrimSpace(string(output)))
+	}
+	return nil
+}
+
+// desktopCommand returns the command line that shows a notification on goos.
+func desktopCommand(goos string, subject string, body string) ([]string, error) {
+	switch goos {
+	case "linux", "freebsd", "openbsd", "netbsd":
+		return []string{"notify-send", "--app-name=disk-hog-backup", subject, body}, nil
+	case "darwin":
+		script := fmt.Sprintf("display notification %v with title %v", appleScriptString(body), appleScriptString(subject))
+		return []string{"osascript", "-e", script}, nil
+	case "windows":
+		script := fmt.Sprintf(windowsToastScript, powerShellString(subject), powerShellString(body))
+		return []string{"powershell", "-NoProfile", "-NonInteractive", "-Command", script}, nil
+	}
+	return nil, fmt.Errorf("desktop notifications aren't supported on %v", goos)
+}
+
+func appleScriptString(s string) string {
+	return `"` + strings.NewReplacer(`\`, `\\`, `"`, `\"`).Replace(s) + `"`
+}
+
+// windowsToastScript shows a toast through the Windows Runtime API, which
+// needs nothing installed. Toasts have to come from a registered app, so it
+// uses PowerShell's own ID.
+const windowsToastScript = `[Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime] > $null
+$toast = [Windows.UI.Notifications.ToastNotificationManager]::GetTemplateContent([Windows.UI.Notifications.ToastTemplateType]::ToastText02)
+$text = $toast.GetElementsByTagName('text')
+$text.Item(0).AppendChild($toast.CreateTextNode(%v)) > $null
+$text.Item(1).AppendChild($toast.CreateTextNode(%v)) > $null
+$notifier = [Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier('{1AC14E77-02E7-4E5D-B744-2EB1AE5198B7}\WindowsPowerShell\v1.0\powershell.exe')
+$notifier.Show([Windows.UI.Notifications.ToastNotification]::new($toast))`
+
+// powerShellString quotes s as a PowerShell literal string, in which nothing
+// is special but the quote itself.
+func powerShellString(s string) string {
+	return "'" + strings.Replace(s, "'", "''", -1) + "'"
+}
@@ -0,0 +1,35 @@
+package notify
+
+import (
+	"github.com/stretchr/testify/assert"
+	"testing"
+)
+
+func TestDesktopCommandOnLinux(t *testing.T) {
+	command, err := desktopCommand("linux", "backup succeeded", "copied 3 files")
+
+	assert.NoError(t, err)
+	assert.Equal(t, []string{"notify-send", "--app-name=disk-hog-backup", "backup succeeded", "copied 3 files"}, command)
+}
+
+func TestDesktopCommandOnMacEscapesQuotes(t *testing.T) {
+	command, err := desktopCommand("darwin", "backup failed", `couldn't read "a\b"`)
+
+	assert.NoError(t, err)
+	assert.Equal(t, []string{"osascript", "-e", `display notification "couldn't read \"a\\b\"" with title "backup failed"`}, command)
+}
+
+func TestDesktopCommandOnWindowsEscapesQuotes(t *testing.T) {
+	command, err := desktopCommand("windows", "backup failed", `couldn't read C:\a`)
+
+	assert.NoError(t, err)
+	assert.Equal(t, []string{"powershell", "-NoProfile", "-NonInteractive", "-Command"}, command[:4])
+	assert.Contains(t, command[4], `CreateTextNode('backup failed')`)
+	assert.Contains(t, command[4], `CreateTextNode('couldn''t read C:\a')`)
+}
+
+func TestDesktopCommandUnsupported(t *testing.T) {
+	_, err := desktopCommand("plan9", "backup succeeded", "")
+
+	assert.Error(t, err)
+}
@@ -0,0 +1,3 @@
+// Package notify tells people and monitoring systems how a backup went, by
+// email, healthchecks.io style pings and webhooks.
+package notify
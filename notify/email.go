@@ -0,0 +1,47 @@
+package notify
+
+import (
+	"mime"
+	"net"
+	"net/smtp"
+	"os"
+	"strings"
+	"time"
+)
+
+// Email sends notifications through an SMTP server. Credentials, if the
+// server needs them, come from the DHB_SMTP_USER and DHB_SMTP_PASSWORD
+// environment variables so they don't show up in process listings.
+type Email struct {
+	To     string
+	From   string
+	Server string
+}
+
+func (e Email) Send(subject string, body string) error {
+	host, _, err := net.SplitHostPort(e.Server)
+	if err != nil {
+		return err
+	}
+	var auth smtp.Auth
+	if user := os.Getenv("DHB_SMTP_USER"); user != "" {
+		auth = smtp.PlainAuth("", user, os.Getenv("DHB_SMTP_PASSWORD"), host)
+	}
+	message := e.message(subject, body, time.Now())
+	return smtp.SendMail(e.Server, auth, e.From, []string{e.To}, []byte(message))
+}
+
+// message returns the email to send. Paths in the subject and body can have
+// any characters in them, so the body is sent as UTF-8 and the subject is
+// encoded if need be.
+func (e Email) message(subject string, body string, now time.Time) string {
+	return "To: " + e.To + "\r\n" +
+		"From: " + e.From + "\r\n" +
+		"Subject: " + mime.QEncoding.Encode("utf-8", subject) + "\r\n" +
+		"Date: " + now.Format(time.RFC1123Z) + "\r\n" +
+		"MIME-Version: 1.0\r\n" +
+		"Content-Type: text/plain; charset=UTF-8\r\n" +
+		"Content-Transfer-Encoding: 8bit\r\n" +
+		"\r\n" +
+		strings.ReplaceAll(body, "\n", "\r\n")
+}
@@ -0,0 +1,26 @@
+package notify
+
+import (
+	"github.com/stretchr/testify/assert"
+	"testing"
+	"time"
+)
+
+func TestEmailMessage(t *testing.T) {
+	email := Email{To: "me@example.com", From: "dhb@example.com"}
+	now := time.Date(2024, 6, 1, 9, 30, 0, 0, time.UTC)
+
+	message := email.message("disk-hog-backup of /home/zoë failed", "couldn't read /home/zoë/café.txt\n", now)
+
+	assert.Contains(t, message, "\r\nSubject: =?utf-8?q?disk-hog-backup_of_/home/zo=C3=AB_failed?=\r\n")
+	assert.Contains(t, message, "\r\nDate: Sat, 01 Jun 2024 09:30:00 +0000\r\n")
+	assert.Contains(t, message, "\r\nMIME-Version: 1.0\r\n")
+	assert.Contains(t, message, "\r\nContent-Type: text/plain; charset=UTF-8\r\n")
+	assert.Contains(t, message, "\r\n\r\ncouldn't read /home/zoë/café.txt\r\n")
+}
+
+func TestEmailSubjectIsOnlyEncodedWhenNeeded(t *testing.T) {
+	message := Email{}.message("disk-hog-backup of /home/me succeeded", "", time.Now())
+
+	assert.Contains(t, message, "\r\nSubject: disk-hog-backup of /home/me succeeded\r\n")
+}
@@ -0,0 +1,22 @@
+package notify
+
+import (
+	"fmt"
+	"io"
+	"net/http"
+	"time"
+)
+
+var client = &http.Client{Timeout: 30 * time.Second}
+
+func post(url string, contentType string, body io.Reader) error {
+	response, err := client.Post(url, contentType, body)
+	if err != nil {
+		return err
+	}
+	defer response.Body.Close()
+	if response.StatusCode >= 300 {
+		return fmt.Errorf("%v responded with %v", url, response.Status)
+	}
+	return nil
+}
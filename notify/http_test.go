@@ -0,0 +1,86 @@
+package notify
+
+import (
+	"encoding/json"
+	"errors"
+	"github.com/stretchr/testify/assert"
+	"github.com/timabell/disk-hog-backup/dhcopy"
+	"io/ioutil"
+	"net/http"
+	"net/http/httptest"
+	"testing"
+)
+
+type receivedRequest struct {
+	path string
+	body string
+}
+
+func recordRequests(status int) (*httptest.Server, *[]receivedRequest) {
+	var received []receivedRequest
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		body, _ := ioutil.ReadAll(r.Body)
+		received = append(received, receivedRequest{path: r.URL.Path, body: string(body)})
+		w.WriteHeader(status)
+	}))
+	return server, &received
+}
+
+func TestPingPaths(t *testing.T) {
+	server, received := recordRequests(http.StatusOK)
+	defer server.Close()
+	ping := Ping{URL: server.URL + "/check-id"}
+
+	assert.NoError(t, ping.Start())
+	assert.NoError(t, ping.Finish(Result{Source: "/home"}))
+	assert.NoError(t, ping.Finish(Result{Source: "/home", Err: errors.New("disk full")}))
+	assert.NoError(t, ping.Finish(Result{Source: "/home", Stats: &dhcopy.Stats{Errors: []error{errors.New("read failed")}}}))
+
+	if assert.Len(t, *received, 4) {
+		assert.Equal(t, "/check-id/start", (*received)[0].path)
+		assert.Equal(t, "/check-id", (*received)[1].path)
+		assert.Equal(t, "/check-id/fail", (*received)[2].path)
+		assert.Contains(t, (*received)[2].body, "disk full")
+		assert.Equal(t, "/check-id/fail", (*received)[3].path, "partial runs should be reported as failures")
+	}
+}
+
+func TestWebhookPostsJson(t *testing.T) {
+	server, received := recordRequests(http.StatusOK)
+	defer server.Close()
+
+	err := Webhook{URL: server.URL}.Finish(Result{Source: "/home", Destination: "/mnt/backups", Err: errors.New("disk full")})
+	assert.NoError(t, err)
+
+	if assert.Len(t, *received, 1) {
+		var event map[string]interface{}
+		assert.NoError(t, json.Unmarshal([]byte((*received)[0].body), &event))
+		assert.Equal(t, float64(webhookSchemaVersion), event["schema_version"])
+		assert.Equal(t, "failure", event["event"])
+		assert.Equal(t, "/home", event["source"])
+		assert.Equal(t, "disk full", event["error"])
+	}
+}
+
+func TestWebhookSendsStats(t *testing.T) {
+	server, received := recordRequests(http.StatusOK)
+	defer server.Close()
+	stats := &dhcopy.Stats{FilesCopied: 3, BytesCopied: 1024, DefaultSkipped: 1, FilterSkipped: 2, Errors: []error{errors.New("read failed")}}
+
+	err := Webhook{URL: server.URL}.Finish(Result{Source: "/home", Destination: "/mnt/backups", Stats: stats})
+	assert.NoError(t, err)
+
+	if assert.Len(t, *received, 1) {
+		var event map[string]interface{}
+		assert.NoError(t, json.Unmarshal([]byte((*received)[0].body), &event))
+		assert.Equal(t, "partial", event["event"])
+		assert.Equal(t, map[string]interface{}{"files": float64(3), "bytes": float64(1024), "skipped": float64(3), "errors": float64(1)}, event["stats"])
+	}
+}
+
+func TestErrorStatusIsReported(t *testing.T) {
+	server, _ := recordRequests(http.StatusInternalServerError)
+	defer server.Close()
+
+	assert.Error(t, Webhook{URL: server.URL}.Start(Result{}))
+}
@@ -0,0 +1,23 @@
+package notify
+
+import "strings"
+
+// Ping reports to a healthchecks.io style monitoring URL: /start is appended
+// when a backup begins, /fail when it fails or some files couldn't be backed
+// up, and the URL itself is pinged on success. The result body is sent along
+// as the log.
+type Ping struct {
+	URL string
+}
+
+func (p Ping) Start() error {
+	return post(strings.TrimRight(p.URL, "/")+"/start", "text/plain", nil)
+}
+
+func (p Ping) Finish(result Result) error {
+	url := p.URL
+	if result.Outcome() != OutcomeSuccess {
+		url = strings.TrimRight(p.URL, "/") + "/fail"
+	}
+	return post(url, "text/plain", strings.NewReader(result.Body()))
+}
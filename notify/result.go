@@ -0,0 +1,87 @@
+package notify
+
+import (
+	"fmt"
+	"github.com/timabell/disk-hog-backup/dhcopy"
+	"strings"
+)
+
+// Result describes how a backup run went, for sending to notifiers.
+type Result struct {
+	Source      string
+	Destination string
+	SetName     string
+
+	// Stats is what was copied, nil if the backup failed before it started
+	// copying.
+	Stats *dhcopy.Stats
+
+	Err error
+}
+
+// Outcomes of a backup run, as returned by Result.Outcome.
+const (
+	OutcomeSuccess = "success"
+	OutcomePartial = "partial"
+	OutcomeFailure = "failure"
+)
+
+// Outcome is OutcomeFailure if the backup failed, OutcomePartial if it
+// finished but some files or folders couldn't be backed up, or
+// OutcomeSuccess.
+func (r Result) Outcome() string {
+	if r.Err != nil {
+		return OutcomeFailure
+	}
+	if len(r.fileErrors()) > 0 {
+		return OutcomePartial
+	}
+	return OutcomeSuccess
+}
+
+func (r Result) Subject() string {
+	switch r.Outcome() {
+	case OutcomeFailure:
+		return fmt.Sprintf("disk-hog-backup of %v failed", r.Source)
+	case OutcomePartial:
+		return fmt.Sprintf("disk-hog-backup of %v was partial, %v errors", r.Source, len(r.fileErrors()))
+	}
+	return fmt.Sprintf("disk-hog-backup of %v succeeded", r.Source)
+}
+
+func (r Result) Body() string {
+	lines := []string{
+		fmt.Sprintf("source: %v", r.Source),
+		fmt.Sprintf("destination: %v", r.Destination),
+	}
+	if r.SetName != "" {
+		lines = append(lines, fmt.Sprintf("set: %v", r.SetName))
+	}
+	if r.Err != nil {
+		lines = append(lines, fmt.Sprintf("error: %v", r.Err))
+	}
+	lines = append(lines, r.summary()...)
+	if errs := r.fileErrors(); len(errs) > 0 {
+		lines = append(lines, "couldn't back up:")
+		for _, err := range errs {
+			lines = append(lines, fmt.Sprintf("  %v", err))
+		}
+	}
+	return strings.Join(lines, "\n") + "\n"
+}
+
+func (r Result) summary() []string {
+	if r.Stats == nil {
+		return nil
+	}
+	return r.Stats.Summary()
+}
+
+// fileErrors returns what couldn't be backed up when carrying on after
+// errors.
+func (r Result) fileErrors() []error {
+	if r.Stats == nil {
+		return nil
+	}
+	return r.Stats.Errors
+}
@@ -0,0 +1,33 @@
+package notify
+
+import (
+	"errors"
+	"github.com/stretchr/testify/assert"
+	"github.com/timabell/disk-hog-backup/dhcopy"
+	"testing"
+)
+
+func TestSuccessMessage(t *testing.T) {
+	result := Result{Source: "/home", Destination: "/mnt/backups", SetName: "dhb-set-20010203-140506", Stats: &dhcopy.Stats{FilesCopied: 1, BytesCopied: 10}}
+
+	assert.Equal(t, OutcomeSuccess, result.Outcome())
+	assert.Equal(t, "disk-hog-backup of /home succeeded", result.Subject())
+	assert.Equal(t, "source: /home\ndestination: /mnt/backups\nset: dhb-set-20010203-140506\ncopied 1 files, 10 B\n", result.Body())
+}
+
+func TestPartialMessage(t *testing.T) {
+	stats := &dhcopy.Stats{Errors: []error{errors.New("read failed on /home/a"), errors.New("read failed on /home/b")}}
+	result := Result{Source: "/home", Destination: "/mnt/backups", Stats: stats}
+
+	assert.Equal(t, OutcomePartial, result.Outcome())
+	assert.Equal(t, "disk-hog-backup of /home was partial, 2 errors", result.Subject())
+	assert.Contains(t, result.Body(), "couldn't back up:\n  read failed on /home/a\n  read failed on /home/b\n")
+}
+
+func TestFailureMessage(t *testing.T) {
+	result := Result{Source: "/home", Destination: "/mnt/backups", Err: errors.New("disk full")}
+
+	assert.Equal(t, OutcomeFailure, result.Outcome())
+	assert.Equal(t, "disk-hog-backup of /home failed", result.Subject())
+	assert.Contains(t, result.Body(), "error: disk full\n")
+}
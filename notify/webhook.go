@@ -0,0 +1,69 @@
+package notify
+
+import (
+	"bytes"
+	"encoding/json"
+)
+
+// Webhook posts a JSON event to a URL when a backup starts and finishes.
+type Webhook struct {
+	URL string
+}
+
+// webhookSchemaVersion is sent as schema_version in every event.
+const webhookSchemaVersion = 1
+
+type webhookEvent struct {
+	SchemaVersion int           `json:"schema_version"`
+	Event         string        `json:"event"`
+	Source        string        `json:"source"`
+	Destination   string        `json:"destination"`
+	SetName       string        `json:"set_name,omitempty"`
+	Stats         *webhookStats `json:"stats,omitempty"`
+	Summary       []string      `json:"summary,omitempty"`
+	Error         string        `json:"error,omitempty"`
+}
+
+// webhookStats counts what was copied, in finish events.
+type webhookStats struct {
+	Files   int   `json:"files"`
+	Bytes   int64 `json:"bytes"`
+	Skipped int   `json:"skipped"`
+	Errors  int   `json:"errors"`
+}
+
+func (w Webhook) Start(result Result) error {
+	return w.send("start", result)
+}
+
+// Finish sends a success, partial or failure event, see Result.Outcome.
+func (w Webhook) Finish(result Result) error {
+	return w.send(result.Outcome(), result)
+}
+
+func (w Webhook) send(event string, result Result) error {
+	payload := webhookEvent{
+		SchemaVersion: webhookSchemaVersion,
+		Event:         event,
+		Source:        result.Source,
+		Destination:   result.Destination,
+		SetName:       result.SetName,
+		Summary:       result.summary(),
+	}
+	if result.Stats != nil {
+		payload.Stats = &webhookStats{
+			Files:   result.Stats.FilesCopied,
+			Bytes:   result.Stats.BytesCopied,
+			Skipped: result.Stats.Skipped(),
+			Errors:  len(result.Stats.Errors),
+		}
+	}
+	if result.Err != nil {
+		payload.Error = result.Err.Error()
+	}
+	body, err := json.Marshal(payload)
+	if err != nil {
+		return err
+	}
+	return post(w.URL, "application/json", bytes.NewReader(body))
+}
@@ -0,0 +1,3 @@
+// Package priority lowers the CPU and I/O priority of the running process so
+// backups don't get in the way of other work.
+package priority
@@ -0,0 +1,22 @@
+package priority
+
+import (
+	"fmt"
+	"strconv"
+)
+
+// idleIOPriority is returned by parseIOPriority for "idle".
+const idleIOPriority = -1
+
+// parseIOPriority checks an I/O priority level as given to SetIOPriority,
+// returning idleIOPriority or the best-effort level.
+func parseIOPriority(level string) (int, error) {
+	if level == "idle" {
+		return idleIOPriority, nil
+	}
+	n, err := strconv.Atoi(level)
+	if err != nil || n < 0 || n > 7 {
+		return 0, fmt.Errorf("invalid I/O priority %q, expected idle or 0-7", level)
+	}
+	return n, nil
+}
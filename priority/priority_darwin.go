@@ -0,0 +1,34 @@
+package priority
+
+import (
+	"log"
+	"syscall"
+)
+
+// from sys/resource.h
+const (
+	prioDarwinProcess = 4
+	prioDarwinBG      = 0x1000
+)
+
+// SetNice sets the CPU scheduling niceness of this process, from -20 (most
+// favourable) to 19 (least).
+func SetNice(nice int) error {
+	return syscall.Setpriority(syscall.PRIO_PROCESS, 0, nice)
+}
+
+// SetIOPriority puts this process in the background band for "idle", which
+// throttles its disk I/O and lowers its CPU priority the same way as Time
+// Machine. macOS has nothing like the best-effort levels, so they're ignored
+// with a warning.
+func SetIOPriority(level string) error {
+	n, err := parseIOPriority(level)
+	if err != nil {
+		return err
+	}
+	if n != idleIOPriority {
+		log.Printf("warning: I/O priority levels aren't supported on macOS, only idle, ignoring -ionice %v\n", level)
+		return nil
+	}
+	return syscall.Setpriority(prioDarwinProcess, 0, prioDarwinBG)
+}
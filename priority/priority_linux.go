@@ -0,0 +1,63 @@
+package priority
+
+import (
+	"io/ioutil"
+	"strconv"
+	"syscall"
+)
+
+// from linux/ioprio.h
+const (
+	ioprioWhoProcess      = 1
+	ioprioClassShift      = 13
+	ioprioClassBestEffort = 2
+	ioprioClassIdle       = 3
+)
+
+// SetNice sets the CPU scheduling niceness of this process, from -20 (most
+// favourable) to 19 (least).
+func SetNice(nice int) error {
+	return forEachThread(func(tid int) error {
+		return syscall.Setpriority(syscall.PRIO_PROCESS, tid, nice)
+	})
+}
+
+// SetIOPriority sets the I/O scheduling priority of this process. level is
+// either "idle", to only use the disk when nothing else wants it, or a
+// best-effort level from 0 (highest) to 7 (lowest).
+func SetIOPriority(level string) error {
+	n, err := parseIOPriority(level)
+	if err != nil {
+		return err
+	}
+	ioprio := ioprioClassIdle << ioprioClassShift
+	if n != idleIOPriority {
+		ioprio = ioprioClassBestEffort<<ioprioClassShift | n
+	}
+	return forEachThread(func(tid int) error {
+		_, _, errno := syscall.Syscall(syscall.SYS_IOPRIO_SET, ioprioWhoProcess, uintptr(tid), uintptr(ioprio))
+		if errno != 0 {
+			return errno
+		}
+		return nil
+	})
+}
+
+// Linux applies priorities per thread, and new threads inherit from the one
+// that created them, so set every thread the Go runtime has started so far.
+func forEachThread(apply func(tid int) error) error {
+	tasks, err := ioutil.ReadDir("/proc/self/task")
+	if err != nil {
+		return err
+	}
+	for _, task := range tasks {
+		tid, err := strconv.Atoi(task.Name())
+		if err != nil {
+			continue
+		}
+		if err := apply(tid); err != nil {
+			return err
+		}
+	}
+	return nil
+}
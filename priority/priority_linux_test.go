@@ -0,0 +1,42 @@
+package priority
+
+import (
+	"fmt"
+	"github.com/stretchr/testify/assert"
+	"os"
+	"os/exec"
+	"syscall"
+	"testing"
+)
+
+func TestSetIOPriorityRejectsInvalidLevel(t *testing.T) {
+	assert.Error(t, SetIOPriority("urgent"))
+	assert.Error(t, SetIOPriority("8"))
+}
+
+func TestSetNiceLowersPriority(t *testing.T) {
+	if os.Getenv("DHB_TEST_SET_NICE") == "1" {
+		setNiceInChild()
+		return
+	}
+	// lowering a priority can't be undone without privileges, so do it in a
+	// child process rather than slowing down the rest of the tests
+	child := exec.Command(os.Args[0], "-test.run=^TestSetNiceLowersPriority$")
+	child.Env = append(os.Environ(), "DHB_TEST_SET_NICE=1")
+	output, err := child.CombinedOutput()
+	assert.NoError(t, err, string(output))
+}
+
+func setNiceInChild() {
+	// lowering our own priority never needs privileges
+	if err := SetNice(19); err != nil {
+		fmt.Println(err)
+		os.Exit(1)
+	}
+	// the raw syscall returns 20 - nice
+	prio, err := syscall.Getpriority(syscall.PRIO_PROCESS, 0)
+	if err != nil || 20-prio != 19 {
+		fmt.Printf("niceness not set, getpriority returned %v, %v\n", prio, err)
+		os.Exit(1)
+	}
+}
@@ -0,0 +1,24 @@
+// +build !linux,!darwin,!windows
+
+package priority
+
+import (
+	"log"
+	"runtime"
+)
+
+// SetNice isn't supported on this platform, so it only logs a warning.
+func SetNice(nice int) error {
+	log.Printf("warning: -nice isn't supported on %v, running at normal priority\n", runtime.GOOS)
+	return nil
+}
+
+// SetIOPriority isn't supported on this platform, so it only checks the
+// level and logs a warning.
+func SetIOPriority(level string) error {
+	if _, err := parseIOPriority(level); err != nil {
+		return err
+	}
+	log.Printf("warning: -ionice isn't supported on %v, running at normal I/O priority\n", runtime.GOOS)
+	return nil
+}
@@ -0,0 +1,63 @@
+package priority
+
+import (
+	"log"
+	"syscall"
+)
+
+// from WinBase.h
+const (
+	idlePriorityClass          = 0x00000040
+	belowNormalPriorityClass   = 0x00004000
+	normalPriorityClass        = 0x00000020
+	aboveNormalPriorityClass   = 0x00008000
+	highPriorityClass          = 0x00000080
+	processModeBackgroundBegin = 0x00100000
+)
+
+var setPriorityClass = syscall.NewLazyDLL("kernel32.dll").NewProc("SetPriorityClass")
+
+// SetNice sets the priority class of this process to the nearest match for a
+// Unix niceness, from -20 (most favourable) to 19 (least).
+func SetNice(nice int) error {
+	var class uintptr = normalPriorityClass
+	switch {
+	case nice >= 10:
+		class = idlePriorityClass
+	case nice > 0:
+		class = belowNormalPriorityClass
+	case nice <= -10:
+		class = highPriorityClass
+	case nice < 0:
+		class = aboveNormalPriorityClass
+	}
+	return setClass(class)
+}
+
+// SetIOPriority puts this process into background mode for "idle", which
+// lowers its disk I/O and memory priority as well as its CPU priority.
+// Windows has nothing like the best-effort levels, so they're ignored with a
+// warning.
+func SetIOPriority(level string) error {
+	n, err := parseIOPriority(level)
+	if err != nil {
+		return err
+	}
+	if n != idleIOPriority {
+		log.Printf("warning: I/O priority levels aren't supported on Windows, only idle, ignoring -ionice %v\n", level)
+		return nil
+	}
+	return setClass(processModeBackgroundBegin)
+}
+
+func setClass(class uintptr) error {
+	process, err := syscall.GetCurrentProcess()
+	if err != nil {
+		return err
+	}
+	ok, _, err := setPriorityClass.Call(uintptr(process), class)
+	if ok == 0 {
+		return err
+	}
+	return nil
+}
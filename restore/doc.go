@@ -0,0 +1,2 @@
+// Package restore copies files back out of a backup set.
+package restore
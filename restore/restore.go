@@ -0,0 +1,69 @@
+package restore
+
+import (
+	"fmt"
+	"github.com/timabell/disk-hog-backup/backup_sets"
+	"github.com/timabell/disk-hog-backup/dhcopy"
+	"github.com/timabell/disk-hog-backup/dherrors"
+	"log"
+	"os"
+	"path/filepath"
+)
+
+// Restore copies files from a backup set into the folder to, keeping their
+// paths within the set. Only files matching pattern (see dhcopy.MatchPath),
+// or inside a folder matching it, are restored. Existing files in to are
+// never overwritten. Returns how many files and symlinks were restored.
+func Restore(setFolder string, pattern string, to string) (int, error) {
+	restored := 0
+	err := filepath.Walk(setFolder, func(path string, info os.FileInfo, err error) error {
+		if err != nil {
+			return dherrors.Wrap(dherrors.OpRead, path, err)
+		}
+		relPath, err := filepath.Rel(setFolder, path)
+		if err != nil {
+			return err
+		}
+		if info.IsDir() || backup_sets.IsMetadataFile(relPath) || !matchesOrInside(pattern, filepath.ToSlash(relPath)) {
+			return nil
+		}
+		dest := filepath.Join(to, relPath)
+		if _, err := os.Lstat(dest); err == nil {
+			return fmt.Errorf("not restoring %v, %v already exists", relPath, dest)
+		}
+		if err := os.MkdirAll(filepath.Dir(dest), os.ModePerm); err != nil {
+			return dherrors.Wrap(dherrors.OpWrite, filepath.Dir(dest), err)
+		}
+		if info.Mode()&os.ModeSymlink != 0 {
+			err = restoreSymlink(path, dest)
+		} else {
+			log.Printf("restoring %v\n", relPath)
+			_, err = dhcopy.CopyFile(path, dest, dhcopy.Options{})
+		}
+		if err != nil {
+			return err
+		}
+		restored++
+		return nil
+	})
+	return restored, err
+}
+
+// matchesOrInside reports whether name, or any folder it's in, matches
+// pattern.
+func matchesOrInside(pattern string, name string) bool {
+	for ; name != "."; name = filepath.ToSlash(filepath.Dir(name)) {
+		if dhcopy.MatchPath(pattern, name) {
+			return true
+		}
+	}
+	return false
+}
+
+func restoreSymlink(link string, dest string) error {
+	target, err := os.Readlink(link)
+	if err != nil {
+		return dherrors.Wrap(dherrors.OpRead, link, err)
+	}
+	return dherrors.Wrap(dherrors.OpLink, dest, os.Symlink(target, dest))
+}
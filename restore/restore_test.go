@@ -0,0 +1,83 @@
+package restore
+
+import (
+	"github.com/stretchr/testify/assert"
+	"github.com/timabell/disk-hog-backup/backup_sets"
+	"github.com/timabell/disk-hog-backup/test_helpers"
+	"io/ioutil"
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestRestoreMatchingFiles(t *testing.T) {
+	set := createSet()
+	defer os.RemoveAll(set)
+	to := test_helpers.CreateTmpFolder("restored")
+	defer os.RemoveAll(to)
+
+	restored, err := Restore(set, "docs/**/*.odt", to)
+
+	assert.NoError(t, err)
+	assert.Equal(t, 2, restored)
+	assertFileContents(t, filepath.Join(to, "docs", "letter.odt"), "dear sir")
+	assertFileContents(t, filepath.Join(to, "docs", "2024", "report.odt"), "findings")
+	_, err = os.Stat(filepath.Join(to, "docs", "notes.txt"))
+	assert.True(t, os.IsNotExist(err), "files not matching the pattern shouldn't be restored")
+}
+
+func TestRestoreFolder(t *testing.T) {
+	set := createSet()
+	defer os.RemoveAll(set)
+	to := test_helpers.CreateTmpFolder("restored")
+	defer os.RemoveAll(to)
+
+	restored, err := Restore(set, "docs/2024", to)
+
+	assert.NoError(t, err)
+	assert.Equal(t, 1, restored)
+	assertFileContents(t, filepath.Join(to, "docs", "2024", "report.odt"), "findings")
+}
+
+func TestRestoreEverythingLeavesOutMetadata(t *testing.T) {
+	set := createSet()
+	defer os.RemoveAll(set)
+	to := test_helpers.CreateTmpFolder("restored")
+	defer os.RemoveAll(to)
+
+	restored, err := Restore(set, "", to)
+
+	assert.NoError(t, err)
+	assert.Equal(t, 3, restored)
+	_, err = os.Stat(filepath.Join(to, backup_sets.ErrorReportName))
+	assert.True(t, os.IsNotExist(err), "set metadata shouldn't be restored")
+}
+
+func TestRestoreDoesNotOverwrite(t *testing.T) {
+	set := createSet()
+	defer os.RemoveAll(set)
+	to := test_helpers.CreateTmpFolder("restored")
+	defer os.RemoveAll(to)
+	test_helpers.WriteFile(filepath.Join(to, "docs", "letter.odt"), "newer letter")
+
+	_, err := Restore(set, "docs/letter.odt", to)
+
+	assert.Error(t, err)
+	assertFileContents(t, filepath.Join(to, "docs", "letter.odt"), "newer letter")
+}
+
+func createSet() string {
+	set := test_helpers.CreateTmpFolder("set")
+	test_helpers.WriteFile(filepath.Join(set, "docs", "letter.odt"), "dear sir")
+	test_helpers.WriteFile(filepath.Join(set, "docs", "notes.txt"), "remember the milk")
+	test_helpers.WriteFile(filepath.Join(set, "docs", "2024", "report.odt"), "findings")
+	test_helpers.WriteFile(filepath.Join(set, backup_sets.ErrorReportName), "read failed on x\n")
+	return set
+}
+
+func assertFileContents(t *testing.T, path string, expected string) {
+	contents, err := ioutil.ReadFile(path)
+	if assert.NoError(t, err) {
+		assert.Equal(t, expected, string(contents))
+	}
+}
@@ -0,0 +1,53 @@
+package main
+
+import (
+	"flag"
+	"fmt"
+	"github.com/timabell/disk-hog-backup/backup_sets"
+	"github.com/timabell/disk-hog-backup/restore"
+	"log"
+	"os"
+	"path/filepath"
+)
+
+// runRestore copies files matching a pattern out of a backup set.
+func runRestore(args []string) {
+	flags := flag.NewFlagSet("restore", flag.ExitOnError)
+	dest := flags.String("destination", "", "destination folder containing backup sets")
+	setName := flags.String("set", "", "name of the set to restore from, defaults to the latest full backup")
+	var before timeFlag
+	flags.Var(&before, "before", "restore from the newest set created before this date/time (e.g. 2024-06-01) or duration ago (e.g. 7d)")
+	pattern := flags.String("path", "", "path or glob within the set to restore, e.g. 'docs/**/*.odt', defaults to everything")
+	to := flags.String("to", "", "folder to restore into, existing files are never overwritten")
+	flags.Parse(args)
+	if *dest == "" || *to == "" {
+		fmt.Fprintln(flags.Output(), "-destination and -to are required")
+		flags.Usage()
+		os.Exit(2)
+	}
+	if *setName != "" && !before.IsZero() {
+		log.Fatal("-set and -before can't be used together")
+	}
+	if !before.IsZero() {
+		var err error
+		*setName, err = backup_sets.LatestSetBefore(*dest, before.Time)
+		if err != nil {
+			log.Fatal(err)
+		}
+	}
+	if *setName == "" {
+		var err error
+		*setName, err = backup_sets.LatestSet(*dest)
+		if err != nil {
+			log.Fatal(err)
+		}
+	}
+	restored, err := restore.Restore(filepath.Join(*dest, *setName), filepath.ToSlash(*pattern), *to)
+	if err != nil {
+		log.Fatalf("Restore failed: %s", err)
+	}
+	if restored == 0 {
+		log.Fatalf("Nothing in %v matched %q", *setName, *pattern)
+	}
+	log.Printf("restored %v files from %v into %v\n", restored, *setName, *to)
+}
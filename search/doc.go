@@ -0,0 +1,3 @@
+// Package search looks for files across all the backup sets in a
+// destination.
+package search
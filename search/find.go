@@ -0,0 +1,72 @@
+package search
+
+import (
+	"github.com/timabell/disk-hog-backup/backup_sets"
+	"github.com/timabell/disk-hog-backup/dhcopy"
+	"github.com/timabell/disk-hog-backup/dherrors"
+	"os"
+	"path/filepath"
+	"strings"
+)
+
+// Found is a file in a backup set.
+type Found struct {
+	Set string
+
+	// Path is slash separated, relative to the set.
+	Path string
+
+	Size int64
+	Hash []byte
+}
+
+// Find lists the files in every finished set in dest whose paths match
+// pattern (see dhcopy.MatchPath), oldest set first. A pattern without a slash
+// matches file names in any folder. Files hardlinked between sets are only
+// hashed once.
+func Find(dest string, pattern string) ([]Found, error) {
+	if !strings.Contains(pattern, "/") {
+		pattern = "**/" + pattern
+	}
+	sets, err := backup_sets.ListSets(dest)
+	if err != nil {
+		return nil, err
+	}
+	var found []Found
+	hashes := map[dhcopy.FileID][]byte{}
+	for _, set := range sets {
+		setFolder := filepath.Join(dest, set)
+		err := filepath.Walk(setFolder, func(path string, info os.FileInfo, err error) error {
+			if err != nil {
+				return dherrors.Wrap(dherrors.OpRead, path, err)
+			}
+			if !info.Mode().IsRegular() {
+				return nil
+			}
+			relPath, err := filepath.Rel(setFolder, path)
+			if err != nil {
+				return err
+			}
+			if backup_sets.IsMetadataFile(relPath) || !dhcopy.MatchPath(pattern, filepath.ToSlash(relPath)) {
+				return nil
+			}
+			id, hasID := dhcopy.FileIDOf(info)
+			hash, hashed := hashes[id]
+			if !hasID || !hashed {
+				hash, err = dhcopy.HashFile(path)
+				if err != nil {
+					return err
+				}
+				if hasID {
+					hashes[id] = hash
+				}
+			}
+			found = append(found, Found{Set: set, Path: filepath.ToSlash(relPath), Size: info.Size(), Hash: hash})
+			return nil
+		})
+		if err != nil {
+			return nil, err
+		}
+	}
+	return found, nil
+}
@@ -0,0 +1,68 @@
+package search
+
+import (
+	"github.com/stretchr/testify/assert"
+	"github.com/timabell/disk-hog-backup/test_helpers"
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestFind(t *testing.T) {
+	dest := createSets()
+	defer os.RemoveAll(dest)
+
+	found, err := Find(dest, "*.odt")
+
+	assert.NoError(t, err)
+	if assert.Len(t, found, 3) {
+		assert.Equal(t, "dhb-set-20010101-000000", found[0].Set)
+		assert.Equal(t, "docs/letter.odt", found[0].Path)
+		assert.Equal(t, int64(len("dear sir")), found[0].Size)
+		assert.NotEmpty(t, found[0].Hash)
+		assert.Equal(t, "dhb-set-20010102-000000", found[1].Set)
+		assert.Equal(t, "dhb-set-20010102-000000", found[2].Set)
+	}
+}
+
+func TestFindWithPath(t *testing.T) {
+	dest := createSets()
+	defer os.RemoveAll(dest)
+
+	found, err := Find(dest, "docs/2001/*")
+
+	assert.NoError(t, err)
+	if assert.Len(t, found, 1) {
+		assert.Equal(t, "docs/2001/report.odt", found[0].Path)
+	}
+}
+
+func TestFindHardlinkedCopies(t *testing.T) {
+	dest := createSets()
+	defer os.RemoveAll(dest)
+	linked := filepath.Join(dest, "dhb-set-20010103-000000", "docs", "letter.odt")
+	if err := os.MkdirAll(filepath.Dir(linked), os.ModePerm); err != nil {
+		panic(err)
+	}
+	if err := os.Link(filepath.Join(dest, "dhb-set-20010102-000000", "docs", "letter.odt"), linked); err != nil {
+		panic(err)
+	}
+
+	found, err := Find(dest, "letter.odt")
+
+	assert.NoError(t, err)
+	if assert.Len(t, found, 3) {
+		assert.Equal(t, found[1].Hash, found[2].Hash, "hardlinked copies have the same hash")
+		assert.NotEqual(t, found[0].Hash, found[1].Hash)
+	}
+}
+
+// createSets makes two sets, the first with a letter and the second with a
+// changed letter and a report.
+func createSets() string {
+	dest := test_helpers.CreateTmpFolder("backups")
+	test_helpers.WriteFile(filepath.Join(dest, "dhb-set-20010101-000000", "docs", "letter.odt"), "dear sir")
+	test_helpers.WriteFile(filepath.Join(dest, "dhb-set-20010102-000000", "docs", "letter.odt"), "dear madam")
+	test_helpers.WriteFile(filepath.Join(dest, "dhb-set-20010102-000000", "docs", "2001", "report.odt"), "findings")
+	return dest
+}
@@ -0,0 +1,73 @@
+package search
+
+import (
+	"bytes"
+	"fmt"
+	"github.com/timabell/disk-hog-backup/backup_sets"
+	"github.com/timabell/disk-hog-backup/dhcopy"
+	"github.com/timabell/disk-hog-backup/dherrors"
+	"os"
+	"path/filepath"
+)
+
+// Version is what a file looked like in one set.
+type Version struct {
+	Set string
+
+	// Status is added (first seen, or back after being missing), changed,
+	// unchanged or missing, compared with the previous set it was in.
+	Status string
+
+	Size int64
+	Hash []byte
+}
+
+// History returns a file's version in each finished set in dest, oldest
+// first, from the first set it appears in. path is relative to the sets.
+// Partial sets, like -changed-since top-ups, only hold files that changed, so
+// they're only listed if the file is in them.
+func History(dest string, path string) ([]Version, error) {
+	sets, err := backup_sets.ListSets(dest)
+	if err != nil {
+		return nil, err
+	}
+	var versions []Version
+	var previous []byte
+	for _, set := range sets {
+		filePath := filepath.Join(dest, set, filepath.FromSlash(path))
+		info, err := os.Lstat(filePath)
+		if os.IsNotExist(err) {
+			partial, err := backup_sets.IsPartial(filepath.Join(dest, set))
+			if err != nil {
+				return nil, err
+			}
+			if partial {
+				continue
+			}
+			if len(versions) > 0 {
+				versions = append(versions, Version{Set: set, Status: "missing"})
+			}
+			previous = nil
+			continue
+		}
+		if err != nil {
+			return nil, dherrors.Wrap(dherrors.OpRead, filePath, err)
+		}
+		if !info.Mode().IsRegular() {
+			return nil, fmt.Errorf("%v in %v isn't a file", path, set)
+		}
+		hash, err := dhcopy.HashFile(filePath)
+		if err != nil {
+			return nil, err
+		}
+		version := Version{Set: set, Status: "unchanged", Size: info.Size(), Hash: hash}
+		if previous == nil {
+			version.Status = "added"
+		} else if !bytes.Equal(hash, previous) {
+			version.Status = "changed"
+		}
+		versions = append(versions, version)
+		previous = hash
+	}
+	return versions, nil
+}
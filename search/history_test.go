@@ -0,0 +1,71 @@
+package search
+
+import (
+	"github.com/stretchr/testify/assert"
+	"github.com/timabell/disk-hog-backup/backup_sets"
+	"github.com/timabell/disk-hog-backup/test_helpers"
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestHistory(t *testing.T) {
+	dest := createSets()
+	defer os.RemoveAll(dest)
+	test_helpers.WriteFile(filepath.Join(dest, "dhb-set-20010103-000000", "docs", "letter.odt"), "dear madam")
+	if err := os.Mkdir(filepath.Join(dest, "dhb-set-20010104-000000"), os.ModePerm); err != nil {
+		panic(err)
+	}
+	test_helpers.WriteFile(filepath.Join(dest, "dhb-set-20010105-000000", "docs", "letter.odt"), "dear madam")
+
+	versions, err := History(dest, "docs/letter.odt")
+
+	assert.NoError(t, err)
+	var statuses []string
+	for _, version := range versions {
+		statuses = append(statuses, version.Set+" "+version.Status)
+	}
+	assert.Equal(t, []string{
+		"dhb-set-20010101-000000 added",
+		"dhb-set-20010102-000000 changed",
+		"dhb-set-20010103-000000 unchanged",
+		"dhb-set-20010104-000000 missing",
+		"dhb-set-20010105-000000 added",
+	}, statuses)
+	assert.Equal(t, int64(len("dear madam")), versions[1].Size)
+}
+
+func TestHistorySkipsPartialSetsWithoutTheFile(t *testing.T) {
+	dest := createSets()
+	defer os.RemoveAll(dest)
+	partialSet := filepath.Join(dest, "dhb-set-20010103-000000")
+	test_helpers.WriteFile(filepath.Join(partialSet, "docs", "other.odt"), "changed since")
+	assert.NoError(t, backup_sets.WriteSetInfo(partialSet, backup_sets.SetInfo{Partial: "changed since 2001-01-02"}))
+	test_helpers.WriteFile(filepath.Join(dest, "dhb-set-20010104-000000", "docs", "letter.odt"), "dear madam")
+
+	versions, err := History(dest, "docs/letter.odt")
+
+	assert.NoError(t, err)
+	var statuses []string
+	for _, version := range versions {
+		statuses = append(statuses, version.Set+" "+version.Status)
+	}
+	assert.Equal(t, []string{
+		"dhb-set-20010101-000000 added",
+		"dhb-set-20010102-000000 changed",
+		"dhb-set-20010104-000000 unchanged",
+	}, statuses, "a partial set without the file doesn't mean it was deleted")
+}
+
+func TestHistoryOfFileAddedLater(t *testing.T) {
+	dest := createSets()
+	defer os.RemoveAll(dest)
+
+	versions, err := History(dest, "docs/2001/report.odt")
+
+	assert.NoError(t, err)
+	if assert.Len(t, versions, 1) {
+		assert.Equal(t, "dhb-set-20010102-000000", versions[0].Set)
+		assert.Equal(t, "added", versions[0].Status)
+	}
+}
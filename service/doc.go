@@ -0,0 +1,2 @@
+// Package service sets up disk-hog-backup to run on a schedule.
+package service
@@ -0,0 +1,58 @@
+package service
+
+import (
+	"fmt"
+	"strings"
+)
+
+// schedules are shorthands for systemd calendar expressions, anything else
+// is passed through to OnCalendar as is.
+var schedules = map[string]string{
+	"hourly": "hourly",
+	"daily":  "daily",
+	"weekly": "weekly",
+}
+
+// SystemdUnits returns the contents of a systemd user service that runs
+// command, and a timer that starts it on schedule. Missed runs, e.g. while a
+// laptop was off, are caught up on when it's next on.
+func SystemdUnits(name string, command []string, schedule string) (service string, timer string) {
+	calendar, found := schedules[schedule]
+	if !found {
+		calendar = schedule
+	}
+	quoted := make([]string, len(command))
+	for i, arg := range command {
+		quoted[i] = systemdQuote(arg)
+	}
+	service = fmt.Sprintf(`[Unit]
+Description=disk-hog-backup (%v)
+
+[Service]
+Type=oneshot
+ExecStart=%v
+Nice=10
+IOSchedulingClass=idle
+`, name, strings.Join(quoted, " "))
+	timer = fmt.Sprintf(`[Unit]
+Description=Run disk-hog-backup (%v) %v
+
+[Timer]
+OnCalendar=%v
+Persistent=true
+
+[Install]
+WantedBy=timers.target
+`, name, schedule, calendar)
+	return service, timer
+}
+
+// systemdQuote quotes an argument for ExecStart, escaping the specifier and
+// variable characters systemd would otherwise expand.
+func systemdQuote(arg string) string {
+	arg = strings.NewReplacer("%", "%%", "$", "$$").Replace(arg)
+	if arg != "" && !strings.ContainsAny(arg, " \t\"'\\;") {
+		return arg
+	}
+	return `"` + strings.NewReplacer(`\`, `\\`, `"`, `\"`).Replace(arg) + `"`
+}
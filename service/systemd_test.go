@@ -0,0 +1,28 @@
+package service
+
+import (
+	"github.com/stretchr/testify/assert"
+	"testing"
+)
+
+func TestSystemdUnits(t *testing.T) {
+	service, timer := SystemdUnits("disk-hog-backup", []string{"/usr/local/bin/disk-hog-backup", "-source", "/home/me/My Documents", "-destination", "/mnt/backups"}, "daily")
+
+	assert.Contains(t, service, "\nExecStart=/usr/local/bin/disk-hog-backup -source \"/home/me/My Documents\" -destination /mnt/backups\n")
+	assert.Contains(t, timer, "\nOnCalendar=daily\n")
+}
+
+func TestSystemdUnitsPassesCalendarExpressionsThrough(t *testing.T) {
+	_, timer := SystemdUnits("disk-hog-backup", []string{"disk-hog-backup"}, "Mon..Fri 18:00")
+
+	assert.Contains(t, timer, "\nOnCalendar=Mon..Fri 18:00\n")
+}
+
+func TestSystemdQuote(t *testing.T) {
+	assert.Equal(t, "plain", systemdQuote("plain"))
+	assert.Equal(t, `"has space"`, systemdQuote("has space"))
+	assert.Equal(t, `"say \"hi\""`, systemdQuote(`say "hi"`))
+	assert.Equal(t, "100%%", systemdQuote("100%"))
+	assert.Equal(t, "$$HOME", systemdQuote("$HOME"))
+	assert.Equal(t, `""`, systemdQuote(""))
+}
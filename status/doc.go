@@ -0,0 +1,3 @@
+// Package status reports on the most recent backup set in a destination,
+// for monitoring.
+package status
@@ -0,0 +1,17 @@
+// +build !windows
+
+package status
+
+import (
+	"os"
+	"syscall"
+)
+
+// linkCount returns how many hardlinks a file has, and which inode they share.
+func linkCount(info os.FileInfo) (uint64, inode, bool) {
+	stat, ok := info.Sys().(*syscall.Stat_t)
+	if !ok {
+		return 0, inode{}, false
+	}
+	return uint64(stat.Nlink), inode{uint64(stat.Dev), uint64(stat.Ino)}, true
+}
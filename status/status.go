@@ -0,0 +1,223 @@
+package status
+
+import (
+	"fmt"
+	"github.com/timabell/disk-hog-backup/backup_sets"
+	"github.com/timabell/disk-hog-backup/dhcopy"
+	"github.com/timabell/disk-hog-backup/dherrors"
+	"os"
+	"path/filepath"
+	"time"
+)
+
+// Level is a Nagios-style check result, which is also the exit code
+// monitoring plugins are expected to return.
+type Level int
+
+const (
+	OK       Level = 0
+	Warning  Level = 1
+	Critical Level = 2
+	Unknown  Level = 3
+)
+
+func (l Level) String() string {
+	switch l {
+	case OK:
+		return "OK"
+	case Warning:
+		return "WARNING"
+	case Critical:
+		return "CRITICAL"
+	}
+	return "UNKNOWN"
+}
+
+// Status describes the most recent backup set at a destination.
+type Status struct {
+	Set        string    `json:"set"`
+	Created    time.Time `json:"created"`
+	AgeSeconds int64     `json:"age_seconds"`
+
+	// Complete is false if the run that made the set didn't finish.
+	Complete bool `json:"complete"`
+
+	SizeBytes int64 `json:"size_bytes"`
+	Errors    int   `json:"errors"`
+
+	// UniqueBytes is the size of the files that aren't hardlinked anywhere
+	// else, i.e. the space that deleting the set would free up. It's only
+	// filled in by AddUniqueBytes, as it means reading through the whole set.
+	UniqueBytes *int64 `json:"unique_bytes,omitempty"`
+}
+
+// SchemaVersion is output as schema_version alongside Status in JSON.
+const SchemaVersion = 1
+
+// Latest reports on the most recent set in dest, as of now, whether or not
+// the run that made it finished. Sets that may still be being written, and
+// partial sets like -changed-since top-ups, are passed over.
+func Latest(dest string, now time.Time) (Status, error) {
+	sets, err := backup_sets.ListAllSets(dest)
+	if err != nil {
+		return Status{}, err
+	}
+	if len(sets) == 0 {
+		return Status{}, fmt.Errorf("no backup sets found in %v", dest)
+	}
+	var latest Status
+	for i := len(sets) - 1; i >= 0 && latest.Set == ""; i-- {
+		setFolder := filepath.Join(dest, sets[i])
+		complete, err := backup_sets.IsComplete(setFolder)
+		if err != nil {
+			return Status{}, err
+		}
+		if !complete {
+			running, err := backup_sets.MayBeRunning(setFolder, now)
+			if err != nil {
+				return Status{}, err
+			}
+			if !running {
+				latest = Status{Set: sets[i]}
+			}
+			continue
+		}
+		partial, err := backup_sets.IsPartial(setFolder)
+		if err != nil {
+			return Status{}, err
+		}
+		if !partial {
+			latest = Status{Set: sets[i], Complete: true}
+		}
+	}
+	if latest.Set == "" {
+		return Status{}, fmt.Errorf("no full backup sets found in %v", dest)
+	}
+	latest.Created, err = backup_sets.ParseSetTime(latest.Set)
+	if err != nil {
+		return Status{}, err
+	}
+	latest.AgeSeconds = int64(now.Sub(latest.Created) / time.Second)
+	setFolder := filepath.Join(dest, latest.Set)
+	if !latest.Complete {
+		return latest, nil
+	}
+	latest.SizeBytes, err = SetSize(setFolder)
+	if err != nil {
+		return Status{}, err
+	}
+	latest.Errors, err = backup_sets.ErrorCount(setFolder)
+	if err != nil {
+		return Status{}, err
+	}
+	return latest, nil
+}
+
+// AddUniqueBytes works out how much of the set isn't hardlinked anywhere
+// else.
+func (s *Status) AddUniqueBytes(dest string) error {
+	unique, err := UniqueBytes(filepath.Join(dest, s.Set))
+	if err != nil {
+		return err
+	}
+	s.UniqueBytes = &unique
+	return nil
+}
+
+// Check compares the status with age thresholds, zero meaning no threshold.
+// A set that had errors is at least a warning, and one that was never
+// finished is critical.
+func (s Status) Check(warnAge time.Duration, critAge time.Duration) Level {
+	if !s.Complete {
+		return Critical
+	}
+	age := time.Duration(s.AgeSeconds) * time.Second
+	if critAge > 0 && age > critAge {
+		return Critical
+	}
+	if warnAge > 0 && age > warnAge {
+		return Warning
+	}
+	if s.Errors > 0 {
+		return Warning
+	}
+	return OK
+}
+
+func (s Status) String() string {
+	age := time.Duration(s.AgeSeconds) * time.Second
+	description := fmt.Sprintf("latest set %v is %v old", s.Set, age)
+	if !s.Complete {
+		return description + " and was never finished"
+	}
+	description += ", " + dhcopy.FormatBytes(s.SizeBytes)
+	if s.UniqueBytes != nil {
+		description += fmt.Sprintf(" (%v unique)", dhcopy.FormatBytes(*s.UniqueBytes))
+	}
+	return description + fmt.Sprintf(", %v errors", s.Errors)
+}
+
+// SetSize returns the size recorded in a set when it was finalized, or adds
+// it up for sets from before that was recorded.
+func SetSize(setFolder string) (int64, error) {
+	info, found, err := backup_sets.ReadSetInfo(setFolder)
+	if err != nil || found {
+		return info.SizeBytes, err
+	}
+	size, _, err := folderSize(setFolder)
+	return size, err
+}
+
+// UniqueBytes returns the size of the files in a set that aren't hardlinked
+// from anywhere outside it, i.e. the space deleting the set would free up.
+// Files hardlinked to each other within the set, by -dedupe, are counted
+// once.
+func UniqueBytes(setFolder string) (int64, error) {
+	_, unique, err := folderSize(setFolder)
+	return unique, err
+}
+
+// inode identifies a file's contents, shared by all its hardlinks.
+type inode struct {
+	device uint64
+	number uint64
+}
+
+// linkedFile is an inode found in a set, and how many of its links are there.
+type linkedFile struct {
+	size       int64
+	links      uint64
+	linksInSet uint64
+}
+
+// folderSize returns the total size of the backed up files in a set, and the
+// size of those with no hardlinks outside it. The set's own metadata files
+// aren't counted, to match the size recorded when a set is finalized.
+func folderSize(setFolder string) (size int64, unique int64, err error) {
+	inodes := map[inode]*linkedFile{}
+	err = filepath.Walk(setFolder, func(path string, info os.FileInfo, err error) error {
+		if err != nil {
+			return dherrors.Wrap(dherrors.OpRead, path, err)
+		}
+		if !info.Mode().IsRegular() || (filepath.Dir(path) == setFolder && backup_sets.IsMetadataFile(info.Name())) {
+			return nil
+		}
+		size += info.Size()
+		links, id, ok := linkCount(info)
+		if !ok || links <= 1 {
+			unique += info.Size()
+			return nil
+		}
+		if inodes[id] == nil {
+			inodes[id] = &linkedFile{size: info.Size(), links: links}
+		}
+		inodes[id].linksInSet++
+		return nil
+	})
+	for _, file := range inodes {
+		if file.linksInSet >= file.links {
+			unique += file.size
+		}
+	}
+	return size, unique, err
+}
@@ -0,0 +1,166 @@
+package status
+
+import (
+	"encoding/json"
+	"github.com/stretchr/testify/assert"
+	"github.com/timabell/disk-hog-backup/backup_sets"
+	"github.com/timabell/disk-hog-backup/test_helpers"
+	"io/ioutil"
+	"os"
+	"path/filepath"
+	"runtime"
+	"testing"
+	"time"
+)
+
+func TestLatest(t *testing.T) {
+	dest := test_helpers.CreateTmpFolder("backups")
+	defer os.RemoveAll(dest)
+	created := time.Date(2001, 2, 3, 14, 5, 6, 0, time.Local)
+	makeSet(dest, created.Add(-24*time.Hour), "")
+	latestSet := makeSet(dest, created, "read failed on x\n")
+
+	latest, err := Latest(dest, created.Add(2*time.Hour))
+
+	assert.NoError(t, err)
+	assert.Equal(t, latestSet, latest.Set)
+	assert.Equal(t, int64(2*60*60), latest.AgeSeconds)
+	assert.Equal(t, int64(len("backmeup susie")), latest.SizeBytes, "the set's error report isn't backed up data")
+	assert.Equal(t, 1, latest.Errors)
+	assert.True(t, latest.Complete)
+	assert.Nil(t, latest.UniqueBytes, "unique bytes are only worked out when asked for")
+}
+
+func TestLatestUsesRecordedSize(t *testing.T) {
+	dest := test_helpers.CreateTmpFolder("backups")
+	defer os.RemoveAll(dest)
+	created := time.Date(2001, 2, 3, 14, 5, 6, 0, time.Local)
+	latestSet := makeSet(dest, created, "")
+	assert.NoError(t, backup_sets.WriteSetInfo(filepath.Join(dest, latestSet), backup_sets.SetInfo{SizeBytes: 1234}))
+
+	latest, err := Latest(dest, created)
+
+	assert.NoError(t, err)
+	assert.Equal(t, int64(1234), latest.SizeBytes)
+}
+
+func TestLatestSkipsPartialSets(t *testing.T) {
+	dest := test_helpers.CreateTmpFolder("backups")
+	defer os.RemoveAll(dest)
+	created := time.Date(2001, 2, 3, 14, 5, 6, 0, time.Local)
+	fullSet := makeSet(dest, created.Add(-24*time.Hour), "")
+	topUp := makeSet(dest, created, "")
+	assert.NoError(t, backup_sets.WriteSetInfo(filepath.Join(dest, topUp), backup_sets.SetInfo{Partial: "only files changed since 2001-02-02 00:00:00"}))
+
+	latest, err := Latest(dest, created)
+
+	assert.NoError(t, err)
+	assert.Equal(t, fullSet, latest.Set)
+}
+
+func TestLatestReportsUnfinishedRun(t *testing.T) {
+	dest := test_helpers.CreateTmpFolder("backups")
+	defer os.RemoveAll(dest)
+	created := time.Date(2001, 2, 3, 14, 5, 6, 0, time.Local)
+	makeSet(dest, created.Add(-24*time.Hour), "")
+	crashed, err := backup_sets.CreateEmptySet(dest, func() time.Time { return created })
+	assert.NoError(t, err)
+
+	latest, err := Latest(dest, created.Add(time.Hour))
+	assert.NoError(t, err)
+	assert.NotEqual(t, crashed, latest.Set, "a set still being written has no result yet")
+
+	marker, err := json.Marshal(backup_sets.RunInfo{PID: 1234, Host: "another-machine", Started: created})
+	assert.NoError(t, err)
+	if err := ioutil.WriteFile(filepath.Join(dest, crashed, backup_sets.IncompleteMarkerName), marker, os.ModePerm); err != nil {
+		panic(err)
+	}
+	latest, err = Latest(dest, created.Add(48*time.Hour))
+
+	assert.NoError(t, err)
+	assert.Equal(t, crashed, latest.Set)
+	assert.False(t, latest.Complete)
+	assert.Equal(t, Critical, latest.Check(0, 0))
+}
+
+func TestUniqueBytesLeavesOutHardlinkedFiles(t *testing.T) {
+	if runtime.GOOS == "windows" {
+		t.Skip("link counts aren't available on Windows")
+	}
+	dest := test_helpers.CreateTmpFolder("backups")
+	defer os.RemoveAll(dest)
+	created := time.Date(2001, 2, 3, 14, 5, 6, 0, time.Local)
+	previousSet := makeSet(dest, created.Add(-24*time.Hour), "")
+	latestSet := makeSet(dest, created, "")
+	if err := os.Link(filepath.Join(dest, previousSet, "testfile.txt"), filepath.Join(dest, latestSet, "linked.txt")); err != nil {
+		panic(err)
+	}
+
+	latest, err := Latest(dest, created)
+	assert.NoError(t, err)
+	assert.NoError(t, latest.AddUniqueBytes(dest))
+
+	assert.Equal(t, int64(2*len("backmeup susie")), latest.SizeBytes)
+	if assert.NotNil(t, latest.UniqueBytes) {
+		assert.Equal(t, int64(len("backmeup susie")), *latest.UniqueBytes)
+	}
+}
+
+func TestUniqueBytesCountsFilesLinkedWithinTheSet(t *testing.T) {
+	if runtime.GOOS == "windows" {
+		t.Skip("link counts aren't available on Windows")
+	}
+	dest := test_helpers.CreateTmpFolder("backups")
+	defer os.RemoveAll(dest)
+	setFolder := filepath.Join(dest, makeSet(dest, time.Date(2001, 2, 3, 14, 5, 6, 0, time.Local), ""))
+	if err := os.Link(filepath.Join(setFolder, "testfile.txt"), filepath.Join(setFolder, "duplicate.txt")); err != nil {
+		panic(err)
+	}
+
+	unique, err := UniqueBytes(setFolder)
+
+	assert.NoError(t, err)
+	assert.Equal(t, int64(len("backmeup susie")), unique, "deleting the set frees a file only linked within it, once")
+}
+
+func TestLatestWithNoSets(t *testing.T) {
+	dest := test_helpers.CreateTmpFolder("backups")
+	defer os.RemoveAll(dest)
+
+	_, err := Latest(dest, time.Now())
+
+	assert.Error(t, err)
+}
+
+func TestCheck(t *testing.T) {
+	fresh := Status{AgeSeconds: 60, Complete: true}
+	assert.Equal(t, OK, fresh.Check(time.Hour, 2*time.Hour))
+	assert.Equal(t, OK, fresh.Check(0, 0))
+
+	stale := Status{AgeSeconds: 90 * 60, Complete: true}
+	assert.Equal(t, Warning, stale.Check(time.Hour, 2*time.Hour))
+	assert.Equal(t, Critical, stale.Check(30*time.Minute, time.Hour))
+
+	partial := Status{AgeSeconds: 60, Complete: true, Errors: 3}
+	assert.Equal(t, Warning, partial.Check(time.Hour, 2*time.Hour))
+
+	unfinished := Status{AgeSeconds: 60}
+	assert.Equal(t, Critical, unfinished.Check(time.Hour, 2*time.Hour))
+}
+
+func makeSet(dest string, created time.Time, errorReport string) string {
+	name := backup_sets.GenerateName(func() time.Time { return created })
+	setFolder := filepath.Join(dest, name)
+	if err := os.Mkdir(setFolder, os.ModePerm); err != nil {
+		panic(err)
+	}
+	if err := ioutil.WriteFile(filepath.Join(setFolder, "testfile.txt"), []byte("backmeup susie"), os.ModePerm); err != nil {
+		panic(err)
+	}
+	if errorReport != "" {
+		if err := ioutil.WriteFile(filepath.Join(setFolder, backup_sets.ErrorReportName), []byte(errorReport), os.ModePerm); err != nil {
+			panic(err)
+		}
+	}
+	return name
+}
@@ -3,6 +3,8 @@ package test_helpers
 import (
 	"io/ioutil"
 	"log"
+	"os"
+	"path/filepath"
 	"time"
 )
 
@@ -34,6 +36,45 @@ func readContents(path string) (string, error) {
 	return string(contents), nil
 }
 
+// WriteFile writes a file with the given contents, creating the folders
+// leading to it.
+func WriteFile(path string, contents string) {
+	if err := os.MkdirAll(filepath.Dir(path), os.ModePerm); err != nil {
+		log.Fatal(err)
+	}
+	if err := ioutil.WriteFile(path, []byte(contents), 0666); err != nil {
+		log.Fatal(err)
+	}
+}
+
+// SameFile reports whether two paths are hardlinks to the same file.
+func SameFile(path1 string, path2 string) bool {
+	info1, err := os.Stat(path1)
+	if err != nil {
+		log.Fatal(err)
+	}
+	info2, err := os.Stat(path2)
+	if err != nil {
+		log.Fatal(err)
+	}
+	return os.SameFile(info1, info2)
+}
+
+// MakeSymlink makes a symlink at path pointing to target.
+func MakeSymlink(target string, path string) {
+	if err := os.Symlink(target, path); err != nil {
+		log.Fatal(err)
+	}
+}
+
+// SetModified sets the modification time of a file, e.g. so that files
+// written moments apart count as identical.
+func SetModified(path string, modified time.Time) {
+	if err := os.Chtimes(path, modified, modified); err != nil {
+		log.Fatal(err)
+	}
+}
+
 // returns a function that always returns the same time
 func TimeFixer() func() time.Time {
 	fixedTime := time.Now()